@@ -0,0 +1,127 @@
+//! Compares the slider move backends.
+//! Magic and hyperbola quintessence are always benchmarked. PEXT is benchmarked if the CPU supports BMI2.
+//! `get_rook_moves` and `get_bishop_moves` use whichever backend the enabled features and runtime CPU detection select.
+
+use std::time::Duration;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use cozy_chess::*;
+use cozy_chess_types::{
+    get_bishop_moves_hyperbola, get_bishop_moves_slow, get_bishop_relevant_blockers,
+    get_rook_moves_hyperbola, get_rook_moves_slow, get_rook_relevant_blockers
+};
+
+fn bench(criterion: &mut Criterion, id: &str, elem: usize, mut routine: impl FnMut()) {
+    criterion
+        .benchmark_group("sliders")
+        .throughput(Throughput::Elements(elem as u64))
+        .bench_function(id, |b| b.iter(&mut routine));
+}
+
+/// Build a slider move table for an indexing scheme, like `build.rs` does.
+fn build_table(
+    size: usize,
+    rook_index: impl Fn(Square, BitBoard) -> usize,
+    bishop_index: impl Fn(Square, BitBoard) -> usize
+) -> Vec<BitBoard> {
+    let mut table = vec![BitBoard::EMPTY; size];
+    for square in Square::ALL {
+        for blockers in get_rook_relevant_blockers(square).iter_subsets() {
+            table[rook_index(square, blockers)] = get_rook_moves_slow(square, blockers);
+        }
+        for blockers in get_bishop_relevant_blockers(square).iter_subsets() {
+            table[bishop_index(square, blockers)] = get_bishop_moves_slow(square, blockers);
+        }
+    }
+    table
+}
+
+fn bench_backend(
+    criterion: &mut Criterion,
+    name: &str,
+    blockers: &[BitBoard],
+    rook_moves: impl Fn(Square, BitBoard) -> BitBoard,
+    bishop_moves: impl Fn(Square, BitBoard) -> BitBoard
+) {
+    let elem = Square::NUM * blockers.len();
+    bench(criterion, &format!("rook_{}", name), elem, || {
+        for &square in black_box(&Square::ALL) {
+            for &blockers in black_box(blockers) {
+                black_box(rook_moves(square, blockers));
+            }
+        }
+    });
+    bench(criterion, &format!("bishop_{}", name), elem, || {
+        for &square in black_box(&Square::ALL) {
+            for &blockers in black_box(blockers) {
+                black_box(bishop_moves(square, blockers));
+            }
+        }
+    });
+}
+
+pub fn criterion_benchmark(criterion: &mut Criterion) {
+    let mut state = 0x6D696E75736B656C76696E2062616974u128 | 1;
+    let mut rand = || {
+        state = state.wrapping_mul(0x2360ED051FC65DA44385DF649FCCF645);
+        let rot = (state >> 122) as u32;
+        let xsl = (state >> 64) as u64 ^ state as u64;
+        xsl.rotate_right(rot)
+    };
+
+    let blockers = (0..1000)
+        .map(|_| BitBoard(rand() & rand() & rand()))
+        .collect::<Vec<_>>();
+
+    bench_backend(criterion, "selected", &blockers, get_rook_moves, get_bishop_moves);
+    bench_backend(criterion, "hyperbola", &blockers, get_rook_moves_hyperbola, get_bishop_moves_hyperbola);
+
+    {
+        use cozy_chess_types::magic;
+
+        let table = build_table(magic::SLIDING_MOVE_TABLE_SIZE, magic::get_rook_moves_index, magic::get_bishop_moves_index);
+        bench_backend(
+            criterion,
+            "magic",
+            &blockers,
+            |square, blockers| table[magic::get_rook_moves_index(square, blockers)],
+            |square, blockers| table[magic::get_bishop_moves_index(square, blockers)]
+        );
+    }
+
+    #[cfg(all(not(feature = "pext"), target_arch = "x86_64"))]
+    if is_x86_feature_detected!("bmi2") {
+        use cozy_chess_types::pext;
+
+        let table = build_table(pext::SLIDING_MOVE_TABLE_SIZE, pext::get_rook_moves_index, pext::get_bishop_moves_index);
+        bench_backend(
+            criterion,
+            "pext",
+            &blockers,
+            // SAFETY: BMI2 support was checked above.
+            |square, blockers| table[unsafe { pext::get_rook_moves_index_bmi2(square, blockers) }],
+            |square, blockers| table[unsafe { pext::get_bishop_moves_index_bmi2(square, blockers) }]
+        );
+    }
+
+    #[cfg(feature = "pext")]
+    {
+        use cozy_chess_types::pext;
+
+        let table = build_table(pext::SLIDING_MOVE_TABLE_SIZE, pext::get_rook_moves_index, pext::get_bishop_moves_index);
+        bench_backend(
+            criterion,
+            "pext",
+            &blockers,
+            |square, blockers| table[pext::get_rook_moves_index(square, blockers)],
+            |square, blockers| table[pext::get_bishop_moves_index(square, blockers)]
+        );
+    }
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(100).measurement_time(Duration::from_secs(10));
+    targets = criterion_benchmark
+}
+criterion_main!(benches);
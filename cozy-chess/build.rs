@@ -19,29 +19,61 @@ fn write_moves(
     }
 }
 
+fn write_table(out_file: &mut impl Write, name: &str, table: &[BitBoard]) {
+    write!(out_file, "const {}: &[u64; {}] = &[", name, table.len()).unwrap();
+    for magic in table {
+        write!(out_file, "{},", magic.0).unwrap();
+    }
+    write!(out_file, "];").unwrap();
+}
+
 fn main() {
     println!("cargo:rerun-if-changed=build.rs");
 
-    let mut table = [BitBoard::EMPTY; SLIDING_MOVE_TABLE_SIZE];
-    write_moves(
-        &mut table,
-        get_rook_relevant_blockers,
-        get_rook_moves_index,
-        get_rook_moves_slow
-    );
-    write_moves(
-        &mut table,
-        get_bishop_relevant_blockers,
-        get_bishop_moves_index,
-        get_bishop_moves_slow
-    );
-
     let mut out_file: PathBuf = std::env::var("OUT_DIR").unwrap().into();
     out_file.push("sliding_moves.rs");
     let mut out_file = BufWriter::new(File::create(out_file).unwrap());
-    write!(&mut out_file, "const SLIDING_MOVES: &[u64; {}] = &[", table.len()).unwrap();
-    for magic in &table {
-        write!(&mut out_file, "{},", magic.0).unwrap();
+
+    // The hyperbola feature computes slider moves without a table.
+    if std::env::var_os("CARGO_FEATURE_HYPERBOLA").is_some() {
+        return;
     }
-    write!(&mut out_file, "];").unwrap();
+
+    // Runtime dispatch on x86_64 looks up PEXT moves after the magic moves.
+    let x86_64 = std::env::var("CARGO_CFG_TARGET_ARCH").unwrap() == "x86_64";
+    let pext_feature = std::env::var_os("CARGO_FEATURE_PEXT").is_some();
+    let mut table = Vec::new();
+    if !pext_feature {
+        table.resize(magic::SLIDING_MOVE_TABLE_SIZE, BitBoard::EMPTY);
+        write_moves(
+            &mut table,
+            get_rook_relevant_blockers,
+            magic::get_rook_moves_index,
+            get_rook_moves_slow
+        );
+        write_moves(
+            &mut table,
+            get_bishop_relevant_blockers,
+            magic::get_bishop_moves_index,
+            get_bishop_moves_slow
+        );
+    }
+    if pext_feature || x86_64 {
+        let offset = table.len();
+        table.resize(offset + pext::SLIDING_MOVE_TABLE_SIZE, BitBoard::EMPTY);
+        write_moves(
+            &mut table[offset..],
+            get_rook_relevant_blockers,
+            pext::get_rook_moves_index,
+            get_rook_moves_slow
+        );
+        write_moves(
+            &mut table[offset..],
+            get_bishop_relevant_blockers,
+            pext::get_bishop_moves_index,
+            get_bishop_moves_slow
+        );
+    }
+
+    write_table(&mut out_file, "SLIDING_MOVES", &table);
 }
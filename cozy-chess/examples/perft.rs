@@ -77,11 +77,7 @@ fn main() {
             return;
         }
         if board.is_none() {
-            if let Ok(arg) = Board::from_fen(&arg, false) {
-                board = Some(arg);
-                continue;
-            }
-            if let Ok(arg) = Board::from_fen(&arg, true) {
+            if let Ok(arg) = Board::from_fen(&arg, FenFormat::XFen) {
                 board = Some(arg);
                 continue;
             }
@@ -2,17 +2,73 @@ use crate::*;
 
 use super::zobrist::ZobristBoard;
 
-helpers::simple_error! {
-    /// An error while building a board.
-    pub enum BoardBuilderError {
-        InvalidBoard = "The board is invalid.",
-        InvalidCastlingRights = "The castling rights are invalid.",
-        InvalidEnPassant = "The en passant square is invalid.",
-        InvalidHalfMoveClock = "The halfmove clock is invalid.",
-        InvalidFullmoveNumber = "The fullmove number is invalid."
+/// An error while building a board.
+/// [`BoardBuilder::build`] reports the first error found, and [`BoardBuilder::validate`] reports all of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BoardBuilderError {
+    /// The side has no king.
+    MissingKing(Color),
+    /// The side has more than one king.
+    TooManyKings(Color),
+    /// The side has more than eight pawns.
+    TooManyPawns(Color),
+    /// The side has more than sixteen pieces.
+    TooManyPieces(Color),
+    /// There is a pawn on the first or eighth rank.
+    PawnOnBackRank(Square),
+    /// The side that just moved is in check.
+    OpponentInCheck,
+    /// The side to move is in check from more than two pieces.
+    TooManyCheckers,
+    /// The side to move is in a double check that no single move could have given.
+    ImpossibleCheckers,
+    /// The side has castle rights, but its king is not on its back rank.
+    KingNotOnBackRank(Color),
+    /// The side has a castle right with the rook on this file, but there is no rook
+    /// there on its back rank on that side of the king.
+    CastleRightWithoutRook(Color, File),
+    /// The en passant square is not on the rank a pawn that just moved two squares skipped over.
+    EnPassantWrongRank,
+    /// The en passant square is not behind a pawn that just moved two squares.
+    EnPassantWithoutPawn,
+    /// The en passant square or the square the pawn moved from is occupied.
+    EnPassantSquareOccupied,
+    /// The side to move is in a check that the pawn that just moved could not have given.
+    EnPassantImpossibleCheck,
+    /// The halfmove clock is greater than 100.
+    HalfmoveClockTooLarge,
+    /// The fullmove number is zero.
+    FullmoveNumberZero
+}
+
+impl core::fmt::Display for BoardBuilderError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        use BoardBuilderError::*;
+
+        match self {
+            MissingKing(color) => write!(f, "{:?} has no king.", color),
+            TooManyKings(color) => write!(f, "{:?} has more than one king.", color),
+            TooManyPawns(color) => write!(f, "{:?} has more than eight pawns.", color),
+            TooManyPieces(color) => write!(f, "{:?} has more than sixteen pieces.", color),
+            PawnOnBackRank(square) => write!(f, "The pawn on {} is on a back rank.", square),
+            OpponentInCheck => write!(f, "The side that just moved is in check."),
+            TooManyCheckers => write!(f, "The side to move is in check from more than two pieces."),
+            ImpossibleCheckers => write!(f, "The side to move is in an impossible double check."),
+            KingNotOnBackRank(color) => write!(f, "{:?} has castle rights, but its king is not on its back rank.", color),
+            CastleRightWithoutRook(color, file) => write!(f, "{:?} has a castle right on the {:?} file without a rook.", color, file),
+            EnPassantWrongRank => write!(f, "The en passant square is on the wrong rank."),
+            EnPassantWithoutPawn => write!(f, "The en passant square is not behind a pawn that just moved."),
+            EnPassantSquareOccupied => write!(f, "The en passant square or the square the pawn moved from is occupied."),
+            EnPassantImpossibleCheck => write!(f, "The pawn that just moved could not have given the check."),
+            HalfmoveClockTooLarge => write!(f, "The halfmove clock is greater than 100."),
+            FullmoveNumberZero => write!(f, "The fullmove number is zero.")
+        }
     }
 }
 
+#[cfg(feature = "std")]
+impl std::error::Error for BoardBuilderError {}
+
 /// A board builder to manipulate arbitrary boards.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct BoardBuilder {
@@ -106,6 +162,38 @@ impl BoardBuilder {
         this
     }
 
+    /// Get a builder set to a random chess960 start position.
+    /// The position is chosen uniformly and deterministically from `seed` using [`random::Rng`].
+    /// # Examples
+    /// ```
+    /// # use cozy_chess::*;
+    /// let board = BoardBuilder::random_chess960_startpos(42).build().unwrap();
+    /// let scharnagl_number = board.scharnagl_number(Color::White).unwrap();
+    /// assert_eq!(board.scharnagl_number(Color::Black), Some(scharnagl_number));
+    /// assert_eq!(BoardBuilder::random_chess960_startpos(42).build().unwrap(), board);
+    /// ```
+    pub fn random_chess960_startpos(seed: u64) -> Self {
+        let mut rng = random::Rng::new(seed);
+        Self::chess960_startpos(rng.below(960) as u32)
+    }
+
+    /// Get a builder set to a random double chess960 start position.
+    /// Both setups are chosen uniformly and independently, and deterministically from `seed`
+    /// using [`random::Rng`].
+    /// # Examples
+    /// ```
+    /// # use cozy_chess::*;
+    /// let board = BoardBuilder::random_double_chess960_startpos(42).build().unwrap();
+    /// assert!(board.scharnagl_number(Color::White).is_some());
+    /// assert!(board.scharnagl_number(Color::Black).is_some());
+    /// ```
+    pub fn random_double_chess960_startpos(seed: u64) -> Self {
+        let mut rng = random::Rng::new(seed);
+        let white = rng.below(960) as u32;
+        let black = rng.below(960) as u32;
+        Self::double_chess960_startpos(white, black)
+    }
+
     fn write_piece_config(&mut self, scharnagl_number: u32, color: Color) {
         assert!(scharnagl_number < 960, "Scharnagl number must be in range 0..960");
         
@@ -273,15 +361,54 @@ impl BoardBuilder {
     /// Build a [`Board`] from this builder.
     /// # Errors
     /// This will error if the current state is invalid.
+    /// The error is the first violation reported by [`BoardBuilder::validate`].
     /// # Examples
     /// ```
     /// # use cozy_chess::*;
     /// let builder = BoardBuilder::default().build().unwrap();
     /// assert_eq!(builder, Board::default());
+    ///
+    /// let mut builder = BoardBuilder::default();
+    /// *builder.square_mut(Square::E1) = None;
+    /// assert_eq!(builder.build(), Err(BoardBuilderError::MissingKing(Color::White)));
     /// ```
     pub fn build(&self) -> Result<Board, BoardBuilderError> {
-        use BoardBuilderError::*;
+        let mut error = None;
+        let board = self.check(&mut |violation| {
+            error.get_or_insert(violation);
+        });
+        match error {
+            Some(error) => Err(error),
+            None => Ok(board)
+        }
+    }
 
+    /// Check the builder for errors, calling `listener` with every violation found.
+    /// Violations that depend on other parts of the board being valid are only checked
+    /// if those parts are valid. For example, checks are only validated if both sides
+    /// have exactly one king.
+    /// # Examples
+    /// ```
+    /// # use cozy_chess::*;
+    /// let mut builder = BoardBuilder::default();
+    /// *builder.square_mut(Square::A1) = None;
+    /// *builder.square_mut(Square::A2) = None;
+    /// *builder.square_mut(Square::B1) = Some((Piece::Pawn, Color::White));
+    /// builder.fullmove_number = 0;
+    ///
+    /// let mut violations = Vec::new();
+    /// builder.validate(|violation| violations.push(violation));
+    /// assert_eq!(violations, [
+    ///     BoardBuilderError::PawnOnBackRank(Square::B1),
+    ///     BoardBuilderError::CastleRightWithoutRook(Color::White, File::A),
+    ///     BoardBuilderError::FullmoveNumberZero
+    /// ]);
+    /// ```
+    pub fn validate(&self, mut listener: impl FnMut(BoardBuilderError)) {
+        self.check(&mut listener);
+    }
+
+    fn check(&self, listener: &mut dyn FnMut(BoardBuilderError)) -> Board {
         let mut board = Board {
             inner: ZobristBoard::empty(),
             pinned: BitBoard::EMPTY,
@@ -290,16 +417,16 @@ impl BoardBuilder {
             fullmove_number: 0
         };
 
-        self.add_board          (&mut board).map_err(|_| InvalidBoard)?;
-        self.add_castle_rights  (&mut board).map_err(|_| InvalidCastlingRights)?;
-        self.add_en_passant     (&mut board).map_err(|_| InvalidEnPassant)?;
-        self.add_halfmove_clock (&mut board).map_err(|_| InvalidHalfMoveClock)?;
-        self.add_fullmove_number(&mut board).map_err(|_| InvalidFullmoveNumber)?;
-        
-        Ok(board)
+        self.add_board          (&mut board, listener);
+        self.add_castle_rights  (&mut board, listener);
+        self.add_en_passant     (&mut board, listener);
+        self.add_halfmove_clock (&mut board, listener);
+        self.add_fullmove_number(&mut board, listener);
+
+        board
     }
 
-    fn add_board(&self, board: &mut Board) -> Result<(), ()> {
+    fn add_board(&self, board: &mut Board, listener: &mut dyn FnMut(BoardBuilderError)) {
         for &square in &Square::ALL {
             if let Some((piece, color)) = self.square(square) {
                 board.inner.xor_square(piece, color, square);
@@ -308,57 +435,51 @@ impl BoardBuilder {
         if self.side_to_move != board.side_to_move() {
             board.inner.toggle_side_to_move();
         }
-        if !board.board_is_valid() {
-            return Err(());
+        board.validate_pieces(listener);
+        if !board.kings_are_valid() {
+            return;
         }
+        board.validate_opponent_check(listener);
 
         let (checkers, pinned) = board.calculate_checkers_and_pins(board.side_to_move());
         board.checkers = checkers;
         board.pinned = pinned;
-
-        Ok(())
+        board.validate_checkers(listener);
     }
 
-    fn add_castle_rights(&self, board: &mut Board) -> Result<(), ()> {
+    fn add_castle_rights(&self, board: &mut Board, listener: &mut dyn FnMut(BoardBuilderError)) {
         for &color in &Color::ALL {
             let rights = self.castle_rights[color as usize];
             board.inner.set_castle_right(color, true, rights.short);
             board.inner.set_castle_right(color, false, rights.long);
         }
-        if !board.castle_rights_are_valid() {
-            return Err(());
-        }
-        Ok(())
+        board.validate_castle_rights(listener);
     }
 
-    fn add_en_passant(&self, board: &mut Board) -> Result<(), ()> {
+    fn add_en_passant(&self, board: &mut Board, listener: &mut dyn FnMut(BoardBuilderError)) {
         if let Some(square) = self.en_passant {
             let en_passant_rank = Rank::Third.relative_to(!board.side_to_move());
             if square.rank() != en_passant_rank {
-                return Err(());
+                listener(BoardBuilderError::EnPassantWrongRank);
+                return;
             }
             board.inner.set_en_passant(Some(square.file()));
         }
-        if !board.en_passant_is_valid() {
-            return Err(());
-        }
-        Ok(())
+        board.validate_en_passant(listener);
     }
 
-    fn add_halfmove_clock(&self, board: &mut Board) -> Result<(), ()> {
+    fn add_halfmove_clock(&self, board: &mut Board, listener: &mut dyn FnMut(BoardBuilderError)) {
         board.halfmove_clock = self.halfmove_clock;
         if !board.halfmove_clock_is_valid() {
-            return Err(());
+            listener(BoardBuilderError::HalfmoveClockTooLarge);
         }
-        Ok(())
     }
 
-    fn add_fullmove_number(&self, board: &mut Board) -> Result<(), ()> {
+    fn add_fullmove_number(&self, board: &mut Board, listener: &mut dyn FnMut(BoardBuilderError)) {
         board.fullmove_number = self.fullmove_number;
         if !board.fullmove_number_is_valid() {
-            return Err(());
+            listener(BoardBuilderError::FullmoveNumberZero);
         }
-        Ok(())
     }
 }
 
@@ -370,19 +491,136 @@ mod tests {
     fn roundtrip_board() {
         let positions = include_str!("test_data/valid.sfens");
         for fen in positions.lines() {
-            let board = Board::from_fen(fen, true).unwrap();
+            let board = Board::from_fen(fen, FenFormat::Shredder).unwrap();
             let builder = BoardBuilder::from_board(&board);
             assert_eq!(builder.build().unwrap(), board);
         }
     }
 
+    #[test]
+    fn validate_reports_all_violations() {
+        use BoardBuilderError::*;
+
+        fn violations(fen: &str, edit: impl FnOnce(&mut BoardBuilder)) -> Vec<BoardBuilderError> {
+            let mut builder = BoardBuilder::from_board(&fen.parse().unwrap());
+            edit(&mut builder);
+            let mut violations = Vec::new();
+            builder.validate(|violation| violations.push(violation));
+            assert_eq!(builder.build().err(), violations.first().copied());
+            violations
+        }
+
+        let startpos = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+        assert_eq!(violations(startpos, |_| {}), []);
+        assert_eq!(violations(startpos, |b| {
+            *b.square_mut(Square::E8) = None;
+            *b.square_mut(Square::D1) = Some((Piece::King, Color::White));
+        }), [TooManyKings(Color::White), MissingKing(Color::Black)]);
+        assert_eq!(violations(startpos, |b| {
+            *b.square_mut(Square::A1) = Some((Piece::Pawn, Color::White));
+            *b.square_mut(Square::H8) = Some((Piece::Pawn, Color::Black));
+            *b.square_mut(Square::A2) = None;
+            *b.square_mut(Square::H7) = None;
+            b.halfmove_clock = 101;
+        }), [
+            PawnOnBackRank(Square::A1),
+            PawnOnBackRank(Square::H8),
+            CastleRightWithoutRook(Color::White, File::A),
+            CastleRightWithoutRook(Color::Black, File::H),
+            HalfmoveClockTooLarge
+        ]);
+        assert_eq!(violations(startpos, |b| {
+            *b.square_mut(Square::E4) = Some((Piece::Pawn, Color::Black));
+            *b.square_mut(Square::D4) = Some((Piece::Pawn, Color::Black));
+            *b.square_mut(Square::C4) = Some((Piece::Pawn, Color::Black));
+            *b.square_mut(Square::E1) = Some((Piece::Queen, Color::White));
+            *b.square_mut(Square::E3) = Some((Piece::King, Color::White));
+            b.en_passant = Some(Square::E4);
+        }), [
+            TooManyPieces(Color::White),
+            TooManyPawns(Color::Black),
+            TooManyPieces(Color::Black),
+            KingNotOnBackRank(Color::White),
+            EnPassantWrongRank
+        ]);
+        assert_eq!(violations(startpos, |b| {
+            *b.square_mut(Square::E7) = Some((Piece::Queen, Color::White));
+            *b.square_mut(Square::D1) = None;
+        }), [OpponentInCheck]);
+        assert_eq!(violations("4k3/8/8/8/8/8/8/4K3 b - - 0 1", |b| {
+            *b.square_mut(Square::D6) = Some((Piece::Knight, Color::White));
+            *b.square_mut(Square::F6) = Some((Piece::Knight, Color::White));
+        }), [ImpossibleCheckers]);
+        assert_eq!(violations("4k3/8/8/8/8/8/8/4K3 b - - 0 1", |b| {
+            *b.square_mut(Square::E8) = None;
+            *b.square_mut(Square::E5) = Some((Piece::King, Color::Black));
+            *b.square_mut(Square::A5) = Some((Piece::Rook, Color::White));
+            *b.square_mut(Square::C3) = Some((Piece::Bishop, Color::White));
+            *b.square_mut(Square::F3) = Some((Piece::Knight, Color::White));
+        }), [TooManyCheckers]);
+        assert_eq!(violations("4k3/8/8/8/8/8/8/R3K2R b - - 0 1", |b| {
+            *b.square_mut(Square::E8) = None;
+            *b.square_mut(Square::A4) = Some((Piece::King, Color::Black));
+            b.en_passant = Some(Square::E3);
+        }), [EnPassantWithoutPawn, EnPassantImpossibleCheck]);
+        assert_eq!(violations("4k3/8/8/8/4P3/8/8/4K3 b - - 0 1", |b| {
+            *b.square_mut(Square::E2) = Some((Piece::Knight, Color::White));
+            b.en_passant = Some(Square::E3);
+            b.fullmove_number = 0;
+        }), [EnPassantSquareOccupied, FullmoveNumberZero]);
+    }
+
+    #[test]
+    fn valid_boards_have_no_violations() {
+        for fen in include_str!("test_data/valid.sfens").lines() {
+            let board = Board::from_fen(fen, FenFormat::Shredder).unwrap();
+            BoardBuilder::from_board(&board).validate(|violation| panic!("{}: {}", fen, violation));
+        }
+    }
+
     #[test]
     fn scharnagl_to_board() {
         let positions = include_str!("test_data/chess960_start_positions.sfens");
         for (scharnagl_number, fen) in positions.lines().enumerate() {
-            let board = Board::from_fen(fen, true).unwrap();
+            let board = Board::from_fen(fen, FenFormat::Shredder).unwrap();
             let builder = BoardBuilder::chess960_startpos(scharnagl_number as u32);
             assert_eq!(builder.build().unwrap(), board);
         }
     }
+
+    #[test]
+    fn board_to_scharnagl() {
+        let positions = include_str!("test_data/chess960_start_positions.sfens");
+        for (scharnagl_number, fen) in positions.lines().enumerate() {
+            let board = Board::from_fen(fen, FenFormat::Shredder).unwrap();
+            for &color in &Color::ALL {
+                assert_eq!(board.scharnagl_number(color), Some(scharnagl_number as u32), "{}", fen);
+            }
+        }
+        for fen in include_str!("test_data/valid.sfens").lines() {
+            let board = Board::from_fen(fen, FenFormat::Shredder).unwrap();
+            for &color in &Color::ALL {
+                if let Some(scharnagl_number) = board.scharnagl_number(color) {
+                    let expected = BoardBuilder::chess960_startpos(scharnagl_number);
+                    let back_rank = Rank::First.relative_to(color);
+                    for square in back_rank.bitboard() {
+                        let expected = expected.square(square).map(|(piece, _)| piece);
+                        assert_eq!(board.piece_on(square), expected, "{}", fen);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn random_chess960_startpos() {
+        let mut seen = [false; 960];
+        for seed in 0..10000 {
+            let board = BoardBuilder::random_double_chess960_startpos(seed).build().unwrap();
+            let white = board.scharnagl_number(Color::White).unwrap();
+            assert!(board.scharnagl_number(Color::Black).is_some());
+            seen[white as usize] = true;
+        }
+        assert!(seen.iter().all(|&seen| seen));
+    }
 }
@@ -0,0 +1,266 @@
+use core::convert::TryInto;
+use core::fmt::{Display, Formatter};
+
+use crate::*;
+
+pub(crate) const WHITE_FIGURINES: [char; Piece::NUM] = ['♙', '♘', '♗', '♖', '♕', '♔'];
+pub(crate) const BLACK_FIGURINES: [char; Piece::NUM] = ['♟', '♞', '♝', '♜', '♛', '♚'];
+
+fn figurine(piece: Piece, color: Color) -> char {
+    match color {
+        Color::White => WHITE_FIGURINES[piece as usize],
+        Color::Black => BLACK_FIGURINES[piece as usize]
+    }
+}
+
+/// A diagram of a [`Board`] as an 8x8 grid with coordinates and the side to move.
+/// This `struct` is created by [`Board::diagram`]. See its documentation for more.
+#[derive(Debug, Clone, Copy)]
+pub struct BoardDiagram<'b> {
+    board: &'b Board,
+    unicode: bool,
+    highlights: BitBoard
+}
+
+impl BoardDiagram<'_> {
+    /// Draw the pieces as Unicode figurines instead of FEN letters.
+    pub fn unicode(mut self, unicode: bool) -> Self {
+        self.unicode = unicode;
+        self
+    }
+
+    /// Highlight some squares by surrounding them with brackets.
+    /// Highlights from multiple calls are combined.
+    pub fn highlight(mut self, squares: BitBoard) -> Self {
+        self.highlights |= squares;
+        self
+    }
+}
+
+impl Display for BoardDiagram<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for &rank in Rank::ALL.iter().rev() {
+            write!(f, "{}", char::from(rank))?;
+            for &file in &File::ALL {
+                let square = Square::new(file, rank);
+                let c = match (self.board.piece_on(square), self.board.color_on(square)) {
+                    (Some(piece), Some(color)) if self.unicode => figurine(piece, color),
+                    (Some(piece), Some(Color::White)) => char::from(piece).to_ascii_uppercase(),
+                    (Some(piece), _) => char::from(piece),
+                    _ if self.unicode => '·',
+                    _ => '.'
+                };
+                if self.highlights.has(square) {
+                    write!(f, "[{}]", c)?;
+                } else {
+                    write!(f, " {} ", c)?;
+                }
+            }
+            writeln!(f)?;
+        }
+        write!(f, " ")?;
+        for &file in &File::ALL {
+            write!(f, " {} ", char::from(file))?;
+        }
+        writeln!(f)?;
+        match self.board.side_to_move() {
+            Color::White => write!(f, "White to move"),
+            Color::Black => write!(f, "Black to move")
+        }
+    }
+}
+
+impl Board {
+    /// Display the board as a diagram for logging and terminal output.
+    /// Each rank is drawn on its own line from the eighth rank down, followed by
+    /// the file labels and the side to move. Use [`BoardDiagram::unicode`] for figurines
+    /// and [`BoardDiagram::highlight`] to mark squares such as the last move.
+    /// The diagram can be parsed back with [`BoardBuilder::from_diagram`].
+    /// # Examples
+    /// ```
+    /// # use cozy_chess::*;
+    /// let board = Board::default();
+    /// let diagram = format!("{}", board.diagram().highlight(Square::E2.bitboard()));
+    /// assert_eq!(diagram, "\
+    /// 8 r  n  b  q  k  b  n  r \n\
+    /// 7 p  p  p  p  p  p  p  p \n\
+    /// 6 .  .  .  .  .  .  .  . \n\
+    /// 5 .  .  .  .  .  .  .  . \n\
+    /// 4 .  .  .  .  .  .  .  . \n\
+    /// 3 .  .  .  .  .  .  .  . \n\
+    /// 2 P  P  P  P [P] P  P  P \n\
+    /// 1 R  N  B  Q  K  B  N  R \n  \
+    ///   a  b  c  d  e  f  g  h \n\
+    /// White to move");
+    /// let diagram = format!("{}", board.diagram().unicode(true));
+    /// assert!(diagram.starts_with("8 ♜  ♞  ♝  ♛  ♚  ♝  ♞  ♜ \n"));
+    /// ```
+    pub fn diagram(&self) -> BoardDiagram<'_> {
+        BoardDiagram {
+            board: self,
+            unicode: false,
+            highlights: BitBoard::EMPTY
+        }
+    }
+}
+
+/// An error while parsing a board diagram. See [`BoardBuilder::from_diagram`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiagramParseError {
+    /// The line at this zero-based index is not a rank, the file labels, or the side to move.
+    InvalidLine(usize),
+    /// The square does not contain a piece or an empty square marker.
+    InvalidSquare(Square),
+    /// The diagram has more than eight ranks.
+    TooManyRanks,
+    /// The diagram has fewer than eight ranks.
+    TooFewRanks
+}
+
+impl Display for DiagramParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidLine(line) => write!(f, "Line {} is not a rank, file labels, or side to move.", line),
+            Self::InvalidSquare(square) => write!(f, "The contents of {} are not a piece or an empty square.", square),
+            Self::TooManyRanks => write!(f, "The diagram has more than eight ranks."),
+            Self::TooFewRanks => write!(f, "The diagram has fewer than eight ranks.")
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DiagramParseError {}
+
+impl BoardBuilder {
+    /// Parse a board diagram into a builder.
+    /// This accepts the output of [`Board::diagram`] in both ASCII and Unicode. Each rank is
+    /// a line of eight whitespace-separated squares from the A file to the H file, optionally
+    /// preceded by its rank number. Squares are FEN piece letters, figurines, or `.` or `·` for
+    /// empty squares, and may be surrounded by highlight brackets. Lines of file labels are ignored,
+    /// and a `White to move` or `Black to move` line sets the side to move.
+    ///
+    /// Diagrams have no castle rights, en passant square, or clocks, so these are left empty.
+    /// # Examples
+    /// ```
+    /// # use cozy_chess::*;
+    /// let builder = BoardBuilder::from_diagram("
+    ///     . . . . k . . .
+    ///     . . . . . . . .
+    ///     . . . . . . . .
+    ///     . . . . . . . .
+    ///     . . . . . . . .
+    ///     . . . . . . . .
+    ///     . . . . P . . .
+    ///     . . . . K . . .
+    ///     Black to move
+    /// ").unwrap();
+    /// let board = builder.build().unwrap();
+    /// assert_eq!(format!("{}", board), "4k3/8/8/8/8/8/4P3/4K3 b - - 0 1");
+    /// ```
+    pub fn from_diagram(diagram: &str) -> Result<Self, DiagramParseError> {
+        let mut builder = Self::empty();
+        let mut ranks = 0;
+        for (index, line) in diagram.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.chars().filter(|c| !c.is_whitespace()).eq('a'..='h') {
+                continue;
+            }
+            if line.eq_ignore_ascii_case("white to move") {
+                builder.side_to_move = Color::White;
+                continue;
+            }
+            if line.eq_ignore_ascii_case("black to move") {
+                builder.side_to_move = Color::Black;
+                continue;
+            }
+            if ranks >= Rank::NUM {
+                return Err(DiagramParseError::TooManyRanks);
+            }
+            let rank = Rank::index(Rank::NUM - 1 - ranks);
+            let mut squares = line.split(|c: char| c.is_whitespace() || c == '[' || c == ']')
+                .filter(|s| !s.is_empty())
+                .peekable();
+            let mut label = [0; 4];
+            let label: &str = char::from(rank).encode_utf8(&mut label);
+            if squares.peek() == Some(&label) {
+                squares.next();
+            }
+            let mut files = 0;
+            for (&file, token) in File::ALL.iter().zip(&mut squares) {
+                let square = Square::new(file, rank);
+                let mut chars = token.chars();
+                let c = chars.next().unwrap();
+                if chars.next().is_some() {
+                    return Err(DiagramParseError::InvalidSquare(square));
+                }
+                *builder.square_mut(square) = parse_square(c)
+                    .ok_or(DiagramParseError::InvalidSquare(square))?;
+                files += 1;
+            }
+            if files < File::NUM || squares.next().is_some() {
+                return Err(DiagramParseError::InvalidLine(index));
+            }
+            ranks += 1;
+        }
+        if ranks < Rank::NUM {
+            return Err(DiagramParseError::TooFewRanks);
+        }
+        Ok(builder)
+    }
+}
+
+/// Parse the contents of a square. Returns [`None`] if invalid or `Some(None)` if empty.
+fn parse_square(c: char) -> Option<Option<(Piece, Color)>> {
+    if c == '.' || c == '·' {
+        return Some(None);
+    }
+    for &color in &Color::ALL {
+        for &piece in &Piece::ALL {
+            if figurine(piece, color) == c {
+                return Some(Some((piece, color)));
+            }
+        }
+    }
+    let piece = c.to_ascii_lowercase().try_into().ok()?;
+    let color = if c.is_ascii_uppercase() {
+        Color::White
+    } else {
+        Color::Black
+    };
+    Some(Some((piece, color)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diagram_roundtrip() {
+        for fen in include_str!("test_data/valid.sfens").lines() {
+            let board = Board::from_fen(fen, FenFormat::Shredder).unwrap();
+            let expected = BoardBuilder::from_board(&board);
+            for &unicode in &[false, true] {
+                let diagram = board.diagram().unicode(unicode).highlight(board.checkers() | board.pinned(board.side_to_move()));
+                let builder = BoardBuilder::from_diagram(&format!("{}", diagram)).unwrap();
+                assert_eq!(builder.board, expected.board, "{}", fen);
+                assert_eq!(builder.side_to_move, expected.side_to_move, "{}", fen);
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_diagrams() {
+        let too_many_ranks = ". . . . . . . .\n".repeat(9);
+        let cases = [
+            (". . . . k . . .\n. . . . . . . .", DiagramParseError::TooFewRanks),
+            (&too_many_ranks, DiagramParseError::TooManyRanks),
+            (". . . . . . . .\n. . . . . . .", DiagramParseError::InvalidLine(1)),
+            (". . . . . . . . .", DiagramParseError::InvalidLine(0)),
+            (". . . . x . . .", DiagramParseError::InvalidSquare(Square::E8)),
+            ("8 . . . . kk . . .", DiagramParseError::InvalidSquare(Square::E8))
+        ];
+        for &(diagram, error) in &cases {
+            assert_eq!(BoardBuilder::from_diagram(diagram), Err(error), "{}", diagram);
+        }
+    }
+}
@@ -5,11 +5,15 @@ mod parse;
 mod zobrist;
 mod builder;
 mod validate;
+mod transform;
+mod diagram;
+mod pins;
 
 use zobrist::*;
 pub use movegen::*;
 pub use parse::*;
 pub use builder::*;
+pub use diagram::*;
 
 /// The current state of the game.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -22,6 +26,20 @@ pub enum GameStatus {
     Ongoing
 }
 
+/// A single change to the piece placement made while playing a move.
+/// See [`Board::play_unchecked_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PieceDelta {
+    /// The [`Piece`] that was added or removed.
+    pub piece: Piece,
+    /// The [`Color`] of the piece.
+    pub color: Color,
+    /// The square the piece was added to or removed from.
+    pub square: Square,
+    /// `true` if the piece was added, `false` if it was removed.
+    pub added: bool
+}
+
 helpers::simple_error! {
     /// An error returned when the move played was illegal.
     pub struct IllegalMoveError = "The move played was illegal.";
@@ -92,6 +110,86 @@ impl Board {
         BoardBuilder::double_chess960_startpos(white_scharnagl_number, black_scharnagl_number).build().unwrap()
     }
 
+    /// Get the [Scharnagl number](https://en.wikipedia.org/wiki/Fischer_random_chess_numbering_scheme)
+    /// of some side's back rank. This is the inverse of [`Board::chess960_startpos`].
+    /// Returns [`None`] if the back rank is not fully occupied by that side's pieces
+    /// in a valid chess960 setup. Only the back rank is considered.
+    /// # Examples
+    /// ```
+    /// # use cozy_chess::*;
+    /// let board = Board::double_chess960_startpos(518, 0);
+    /// assert_eq!(board.scharnagl_number(Color::White), Some(518));
+    /// assert_eq!(board.scharnagl_number(Color::Black), Some(0));
+    ///
+    /// let board: Board = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1".parse().unwrap();
+    /// assert_eq!(board.scharnagl_number(Color::White), Some(518));
+    /// let board: Board = "rnbqkbnr/pppppppp/8/8/8/5N2/PPPPPPPP/RNBQKB1R b KQkq - 1 1".parse().unwrap();
+    /// assert_eq!(board.scharnagl_number(Color::White), None);
+    /// ```
+    pub fn scharnagl_number(&self, color: Color) -> Option<u32> {
+        let back_rank = Rank::First.relative_to(color);
+        if self.colors(color) & back_rank.bitboard() != back_rank.bitboard() {
+            return None;
+        }
+        let pieces = File::ALL.map(|file| self.piece_on(Square::new(file, back_rank)).unwrap());
+
+        // Light bishops are on odd files and dark bishops on even files.
+        let is_light = |file: File| file as usize % 2 == 1;
+        let mut bishops = File::ALL.iter().filter(|&&file| pieces[file as usize] == Piece::Bishop);
+        let (first, second) = (*bishops.next()?, *bishops.next()?);
+        if bishops.next().is_some() || is_light(first) == is_light(second) {
+            return None;
+        }
+        let (dark_bishop, light_bishop) = if is_light(second) {
+            (first, second)
+        } else {
+            (second, first)
+        };
+
+        let mut rest = pieces.iter().filter(|&&piece| piece != Piece::Bishop);
+        let mut queen = None;
+        let mut knights = [0; 2];
+        let mut num_knights = 0;
+        let mut rest_pieces = [Piece::Pawn; 3];
+        let mut num_rest = 0;
+        for index in 0..6 {
+            match *rest.next()? {
+                Piece::Queen if queen.is_none() => queen = Some(index),
+                Piece::Knight if num_knights < 2 => {
+                    // Knight indices are counted with the queen removed.
+                    knights[num_knights] = index - queen.map_or(0, |queen| (queen < index) as usize);
+                    num_knights += 1;
+                }
+                piece if num_rest < 3 => {
+                    rest_pieces[num_rest] = piece;
+                    num_rest += 1;
+                }
+                _ => return None
+            }
+        }
+        let queen = queen? as u32;
+        if num_knights != 2 || rest_pieces != [Piece::Rook, Piece::King, Piece::Rook] {
+            return None;
+        }
+
+        let knights = match (knights[0], knights[1]) {
+            (0, 1) => 0,
+            (0, 2) => 1,
+            (0, 3) => 2,
+            (0, 4) => 3,
+            (1, 2) => 4,
+            (1, 3) => 5,
+            (1, 4) => 6,
+            (2, 3) => 7,
+            (2, 4) => 8,
+            (3, 4) => 9,
+            _ => unreachable!()
+        };
+        let light_bishop = light_bishop as u32 / 2;
+        let dark_bishop = dark_bishop as u32 / 2;
+        Some(((knights * 6 + queen) * 4 + dark_bishop) * 4 + light_bishop)
+    }
+
     /// Get a [`BitBoard`] of all the pieces of a certain type.
     /// # Examples
     /// ```
@@ -268,27 +366,86 @@ impl Board {
         self.inner.hash_without_ep()
     }
 
-    /// Get the pinned pieces for the side to move.
-    /// Note that this counts pieces regardless of color.
-    /// This counts any piece preventing check on our king.
+    /// Get the incrementally updated hash of the pawns of both colors.
+    /// This may be used to index pawn structure caches.
+    /// # Examples
+    /// ```
+    /// # use cozy_chess::*;
+    /// let mut board = Board::default();
+    /// board.play("g1f3".parse().unwrap());
+    /// board.play("g8f6".parse().unwrap());
+    /// assert_ne!(board.hash(), Board::default().hash());
+    /// assert_eq!(board.pawn_hash(), Board::default().pawn_hash());
+    /// board.play("e2e4".parse().unwrap());
+    /// assert_ne!(board.pawn_hash(), Board::default().pawn_hash());
+    /// ```
+    #[inline(always)]
+    pub fn pawn_hash(&self) -> u64 {
+        self.inner.pawn_hash()
+    }
+
+    /// Get the incrementally updated hash of the pieces of `color` other than pawns, including the king.
+    /// This may be used to index correction history.
+    /// # Examples
+    /// ```
+    /// # use cozy_chess::*;
+    /// let mut board = Board::default();
+    /// board.play("g1f3".parse().unwrap());
+    /// let start = Board::default();
+    /// assert_ne!(board.non_pawn_hash(Color::White), start.non_pawn_hash(Color::White));
+    /// assert_eq!(board.non_pawn_hash(Color::Black), start.non_pawn_hash(Color::Black));
+    /// ```
+    #[inline(always)]
+    pub fn non_pawn_hash(&self, color: Color) -> u64 {
+        self.inner.non_pawn_hash(color)
+    }
+
+    /// Get the incrementally updated material key.
+    /// The key depends only on the number of pieces of each color and type, not on their squares.
+    /// # Examples
+    /// ```
+    /// # use cozy_chess::*;
+    /// let mut board = Board::default();
+    /// board.play("g1f3".parse().unwrap());
+    /// board.play("g8f6".parse().unwrap());
+    /// assert_eq!(board.material_key(), Board::default().material_key());
+    /// board.play("e2e4".parse().unwrap());
+    /// board.play("d7d5".parse().unwrap());
+    /// board.play("e4d5".parse().unwrap());
+    /// assert_ne!(board.material_key(), Board::default().material_key());
+    /// ```
+    #[inline(always)]
+    pub fn material_key(&self) -> u64 {
+        self.inner.material_key()
+    }
+
+    /// Get the pieces of `color` that are pinned to its king.
+    /// A piece is pinned if it is the only piece between its king and an enemy slider on the same line.
+    /// This works for either color, not just the side to move.
+    /// See [`Board::pinners`] and [`Board::pin_ray`].
     /// # Examples
     /// ```
     /// # use cozy_chess::*;
     /// let board: Board = "8/8/1q4k1/5p2/1n6/3B4/1KP3r1/8 w - - 0 1".parse().unwrap();
-    /// assert_eq!(board.pinned(), bitboard! {
+    /// assert_eq!(board.pinned(Color::White), bitboard! {
+    ///     . . . . . . . .
     ///     . . . . . . . .
     ///     . . . . . . . .
     ///     . . . . . . . .
     ///     . . . . . . . .
-    ///     . X . . . . . .
     ///     . . . . . . . .
     ///     . . X . . . . .
     ///     . . . . . . . .
     /// });
+    /// assert_eq!(board.pinned(Color::Black), Square::F5.bitboard());
     /// ```
-    #[inline(always)]
-    pub fn pinned(&self) -> BitBoard {
-        self.pinned
+    #[inline]
+    pub fn pinned(&self, color: Color) -> BitBoard {
+        if color == self.side_to_move() {
+            self.pinned & self.colors(color)
+        } else {
+            self.calculate_checkers_and_pins(color).1 & self.colors(color)
+        }
     }
 
     /// Get the pieces currently giving check.
@@ -315,6 +472,26 @@ impl Board {
         self.checkers
     }
 
+    /// Get the pieces giving check to `color`'s king.
+    /// This is the same as [`Board::checkers`] for the side to move.
+    /// # Examples
+    /// ```
+    /// # use cozy_chess::*;
+    /// let mut board = Board::default();
+    /// board.play("e2e4".parse().unwrap());
+    /// board.play("f7f6".parse().unwrap());
+    /// board.play("d1h5".parse().unwrap());
+    /// assert_eq!(board.checkers_for(Color::Black), Square::H5.bitboard());
+    /// assert_eq!(board.checkers_for(Color::White), BitBoard::EMPTY);
+    /// ```
+    pub fn checkers_for(&self, color: Color) -> BitBoard {
+        if color == self.side_to_move() {
+            self.checkers
+        } else {
+            self.calculate_checkers_and_pins(color).0
+        }
+    }
+
     /// Get the [halfmove clock](https://www.chessprogramming.org/Halfmove_Clock).
     /// # Examples
     /// ```
@@ -397,7 +574,7 @@ impl Board {
     /// ```
     #[inline(always)]
     pub fn piece_on(&self, square: Square) -> Option<Piece> {
-        Piece::ALL.iter().copied().find(|&p| self.pieces(p).has(square))
+        self.inner.piece_on(square)
     }
 
     /// Get the [`Color`] of the piece on `square`, if there is one.
@@ -432,6 +609,98 @@ impl Board {
             .expect("No king was found.")
     }
 
+    /// Get the [`Piece`] moved by a move, if there is one.
+    /// The move is assumed to be legal.
+    /// # Examples
+    /// ```
+    /// # use cozy_chess::*;
+    /// let board = Board::default();
+    /// assert_eq!(board.moved_piece("g1f3".parse().unwrap()), Some(Piece::Knight));
+    /// ```
+    #[inline(always)]
+    pub fn moved_piece(&self, mv: Move) -> Option<Piece> {
+        Position::moved_piece(self, mv)
+    }
+
+    /// Check if a move is a castling move.
+    /// Castling is encoded as the king capturing its own rook.
+    /// The move is assumed to be legal.
+    /// # Examples
+    /// ```
+    /// # use cozy_chess::*;
+    /// let board: Board = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1".parse().unwrap();
+    /// assert!(board.is_castle("e1h1".parse().unwrap()));
+    /// assert!(board.is_castle("e1a1".parse().unwrap()));
+    /// assert!(!board.is_castle("e1f1".parse().unwrap()));
+    /// ```
+    #[inline(always)]
+    pub fn is_castle(&self, mv: Move) -> bool {
+        Position::is_castle(self, mv)
+    }
+
+    /// Check if a move is an en passant capture.
+    /// The move is assumed to be legal.
+    /// # Examples
+    /// ```
+    /// # use cozy_chess::*;
+    /// let board: Board = "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3"
+    ///     .parse().unwrap();
+    /// assert!(board.is_en_passant("e5f6".parse().unwrap()));
+    /// assert!(!board.is_en_passant("e5d6".parse().unwrap()));
+    /// ```
+    #[inline(always)]
+    pub fn is_en_passant(&self, mv: Move) -> bool {
+        Position::is_en_passant(self, mv)
+    }
+
+    /// Get the [`Piece`] captured by a move, if there is one.
+    /// En passant captures a [`Piece::Pawn`]; castling captures nothing.
+    /// The move is assumed to be legal.
+    /// # Examples
+    /// ```
+    /// # use cozy_chess::*;
+    /// let board: Board = "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3"
+    ///     .parse().unwrap();
+    /// assert_eq!(board.captured_piece("e5f6".parse().unwrap()), Some(Piece::Pawn));
+    /// assert_eq!(board.captured_piece("e5e6".parse().unwrap()), None);
+    /// ```
+    #[inline(always)]
+    pub fn captured_piece(&self, mv: Move) -> Option<Piece> {
+        Position::captured_piece(self, mv)
+    }
+
+    /// Check if a move captures a piece, including en passant captures.
+    /// Castling is not a capture.
+    /// The move is assumed to be legal.
+    /// # Examples
+    /// ```
+    /// # use cozy_chess::*;
+    /// let board: Board = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1".parse().unwrap();
+    /// assert!(board.is_capture("a1a8".parse().unwrap()));
+    /// assert!(!board.is_capture("e1a1".parse().unwrap()));
+    /// ```
+    #[inline(always)]
+    pub fn is_capture(&self, mv: Move) -> bool {
+        Position::is_capture(self, mv)
+    }
+
+    /// Classify a move as a [`MoveKind`].
+    /// The move is assumed to be legal.
+    /// # Examples
+    /// ```
+    /// # use cozy_chess::*;
+    /// let board: Board = "r3k2r/1P6/8/8/8/8/8/R3K2R w KQkq - 0 1".parse().unwrap();
+    /// assert_eq!(board.move_kind("e1h1".parse().unwrap()), MoveKind::Castle);
+    /// assert_eq!(board.move_kind("a1a8".parse().unwrap()), MoveKind::Capture);
+    /// assert_eq!(board.move_kind("b7b8q".parse().unwrap()), MoveKind::Promotion);
+    /// assert_eq!(board.move_kind("b7a8n".parse().unwrap()), MoveKind::CapturePromotion);
+    /// assert_eq!(board.move_kind("a1a2".parse().unwrap()), MoveKind::Quiet);
+    /// ```
+    #[inline(always)]
+    pub fn move_kind(&self, mv: Move) -> MoveKind {
+        Position::move_kind(self, mv)
+    }
+
     /// Get the status of the game.
     /// Note that this game may still be drawn from threefold repetition.
     /// The game may also be drawn from insufficient material cases such
@@ -501,6 +770,7 @@ impl Board {
     /// This differs from the [`Eq`] implementation in that:
     /// - It does not check the halfmove clock or fullmove number
     /// - It ignores the state of the en passant square if it does not apply (capture would not be legal)
+    ///
     /// This method can be used as a strict check for threefold repetition.
     /// # Examples
     /// ```
@@ -519,28 +789,30 @@ impl Board {
     /// assert!(!board_c.same_position(&board_d)); // En passant is legal here
     /// ```
     pub fn same_position(&self, other: &Self) -> bool {
-        fn effective_ep(board: &Board) -> Option<File> {
-            if let Some(ep_file) = board.en_passant() {
-                let color = board.side_to_move();
-                let ep_rank = Rank::Sixth.relative_to(color);
-                let ep_square = Square::new(ep_file, ep_rank);
-                let attackers = get_pawn_attacks(ep_square, !color);
-                for attacker in attackers {
-                    let mv = Move {
-                        from: attacker,
-                        to: ep_square,
-                        promotion: None
-                    };
-                    if board.is_legal(mv) {
-                        return Some(ep_file);
-                    }
+        self.hash_without_ep() == other.hash_without_ep()
+            && self.inner.board_is_equal(&other.inner)
+            && self.effective_en_passant() == other.effective_en_passant()
+    }
+
+    /// The en passant file if an en passant capture is legal, otherwise [`None`].
+    fn effective_en_passant(&self) -> Option<File> {
+        if let Some(ep_file) = self.en_passant() {
+            let color = self.side_to_move();
+            let ep_rank = Rank::Sixth.relative_to(color);
+            let ep_square = Square::new(ep_file, ep_rank);
+            let attackers = get_pawn_attacks(ep_square, !color);
+            for attacker in attackers {
+                let mv = Move {
+                    from: attacker,
+                    to: ep_square,
+                    promotion: None
+                };
+                if self.is_legal(mv) {
+                    return Some(ep_file);
                 }
             }
-            None
         }
-        self.hash_without_ep() == other.hash_without_ep()
-            && self.inner.board_is_equal(&other.inner)
-            && effective_ep(self) == effective_ep(other)
+        None
     }
 
     /// Attempt to play a [null move](https://www.chessprogramming.org/Null_Move),
@@ -659,6 +931,29 @@ impl Board {
     /// assert_eq!(format!("{}", board), EXPECTED);
     /// ```
     pub fn play_unchecked(&mut self, mv: Move) {
+        self.play_unchecked_with(mv, |_| {});
+    }
+
+    /// Version of [`Board::play_unchecked`] that reports every piece placement change.
+    /// The `listener` receives a [`PieceDelta`] for each piece lifted off or dropped onto a square,
+    /// in the order they are applied. Every removal refers to a piece that was on the square and
+    /// every addition refers to an empty square, so applying the deltas in order to the previous
+    /// placement always yields the new placement. This is useful for incrementally updated
+    /// evaluation, such as NNUE accumulators.
+    /// # Panics
+    /// See [`Board::play_unchecked`].
+    /// # Examples
+    /// ```
+    /// # use cozy_chess::*;
+    /// let mut board: Board = "4k3/1P6/8/8/8/8/8/4K3 w - - 0 1".parse().unwrap();
+    /// let mut deltas = Vec::new();
+    /// board.play_unchecked_with("b7b8q".parse().unwrap(), |delta| deltas.push(delta));
+    /// assert_eq!(deltas, [
+    ///     PieceDelta { piece: Piece::Pawn, color: Color::White, square: Square::B7, added: false },
+    ///     PieceDelta { piece: Piece::Queen, color: Color::White, square: Square::B8, added: true }
+    /// ]);
+    /// ```
+    pub fn play_unchecked_with(&mut self, mv: Move, mut listener: impl FnMut(PieceDelta)) {
         self.pinned = BitBoard::EMPTY;
         self.checkers = BitBoard::EMPTY;
 
@@ -694,23 +989,18 @@ impl Board {
             };
 
             // Lift the king, lift the rook.
-            self.inner.xor_square(Piece::King, color, mv.from);
-            self.inner.xor_square(Piece::Rook, color, mv.to);
+            self.xor_square(Piece::King, color, mv.from, &mut listener);
+            self.xor_square(Piece::Rook, color, mv.to, &mut listener);
             // Drop in the king, drop in the rook.
-            self.inner.xor_square(Piece::King, color, Square::new(king, our_back_rank));
-            self.inner.xor_square(Piece::Rook, color, Square::new(rook, our_back_rank));
+            self.xor_square(Piece::King, color, Square::new(king, our_back_rank), &mut listener);
+            self.xor_square(Piece::Rook, color, Square::new(rook, our_back_rank), &mut listener);
             // Remove castling rights.
             self.inner.set_castle_right(color, true, None);
             self.inner.set_castle_right(color, false, None);
         } else {
-            // Lift the piece
-            self.inner.xor_square(moved, color, mv.from);
-            // Drop the piece
-            self.inner.xor_square(moved, color, mv.to);
             if let Some(victim) = victim {
-                // If victim == piece, the piece was XORed out and this puts it back.
-                // If victim != piece, the victim is still there and this XORs it out.
-                self.inner.xor_square(victim, !color, mv.to);
+                // Remove the victim
+                self.xor_square(victim, !color, mv.to, &mut listener);
                 if mv.to.rank() == their_back_rank {
                     let rights = self.inner.castle_rights(!color);
                     if Some(mv.to.file()) == rights.short {
@@ -720,6 +1010,14 @@ impl Board {
                     }
                 }
             }
+            // Lift the piece
+            self.xor_square(moved, color, mv.from, &mut listener);
+            // Drop the piece, replacing it with the promotion if there is one.
+            let dropped = match mv.promotion {
+                Some(promotion) if moved == Piece::Pawn => promotion,
+                _ => moved
+            };
+            self.xor_square(dropped, color, mv.to, &mut listener);
 
             // Finalize the move (special cases for each piece).
             // Updating checker information for non-sliding pieces happens here.
@@ -727,9 +1025,7 @@ impl Board {
                 Piece::Knight => self.checkers |= get_knight_moves(their_king) & mv.to.bitboard(),
                 Piece::Pawn => {
                     if let Some(promotion) = mv.promotion {
-                        // Get rid of the pawn and replace it with the promotion. Also update checkers.
-                        self.inner.xor_square(Piece::Pawn, color, mv.to);
-                        self.inner.xor_square(promotion, color, mv.to);
+                        // Update checkers.
                         if promotion == Piece::Knight {
                             self.checkers |= get_knight_moves(their_king) & mv.to.bitboard();
                         }
@@ -748,7 +1044,7 @@ impl Board {
                                 mv.to.file(),
                                 Rank::Fifth.relative_to(color)
                             );
-                            self.inner.xor_square(Piece::Pawn, !color, victim_square);
+                            self.xor_square(Piece::Pawn, !color, victim_square, &mut listener);
                         }
                         // Update checkers.
                         self.checkers |= get_pawn_attacks(their_king, !color) & mv.to.bitboard();
@@ -758,7 +1054,7 @@ impl Board {
                     self.inner.set_castle_right(color, true, None);
                     self.inner.set_castle_right(color, false, None);
                 }
-                Piece::Rook => if mv.from.rank() == our_back_rank {
+                Piece::Rook if mv.from.rank() == our_back_rank => {
                     let rights = self.inner.castle_rights(color);
                     if Some(mv.from.file()) == rights.short {
                         self.inner.set_castle_right(color, true, None);
@@ -793,11 +1089,19 @@ impl Board {
         
         self.inner.toggle_side_to_move();
     }
+
+    #[inline(always)]
+    fn xor_square(&mut self, piece: Piece, color: Color, square: Square, listener: &mut impl FnMut(PieceDelta)) {
+        let added = !self.colors(color).has(square);
+        self.inner.xor_square(piece, color, square);
+        listener(PieceDelta { piece, color, square, added });
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::*;
 
     #[test]
     fn play_moves() {
@@ -845,6 +1149,61 @@ mod tests {
         }
     }
 
+    #[test]
+    fn move_classification_matches_play() {
+        for board in test_positions() {
+            for_each_move(&board, 3, &mut |board, mv, child| {
+                let us = board.side_to_move();
+                let them = !us;
+                // Work out what the move did from the pieces that changed.
+                let victim = Piece::ALL.iter().copied().find(|&piece| {
+                    child.colored_pieces(them, piece).len() < board.colored_pieces(them, piece).len()
+                });
+                let moved_king = board.colored_pieces(us, Piece::King).has(mv.from);
+                let moved_rook = board.colored_pieces(us, Piece::Rook) != child.colored_pieces(us, Piece::Rook);
+                let castled = moved_king && moved_rook;
+                let en_passant = victim.is_some() && !board.colors(them).has(mv.to);
+                let promoted = child.colored_pieces(us, Piece::Pawn).len() < board.colored_pieces(us, Piece::Pawn).len();
+                let expected = match (castled, en_passant, victim.is_some(), promoted) {
+                    (true, _, _, _) => MoveKind::Castle,
+                    (_, true, _, _) => MoveKind::EnPassant,
+                    (_, _, false, false) => MoveKind::Quiet,
+                    (_, _, true, false) => MoveKind::Capture,
+                    (_, _, false, true) => MoveKind::Promotion,
+                    (_, _, true, true) => MoveKind::CapturePromotion
+                };
+
+                assert_eq!(board.captured_piece(mv), victim, "{} {}", board, mv);
+                assert_eq!(board.is_capture(mv), victim.is_some(), "{} {}", board, mv);
+                assert_eq!(board.is_castle(mv), castled, "{} {}", board, mv);
+                assert_eq!(board.is_en_passant(mv), en_passant, "{} {}", board, mv);
+                assert_eq!(board.move_kind(mv), expected, "{} {}", board, mv);
+            });
+        }
+    }
+
+    #[test]
+    fn piece_deltas_match_play() {
+        for board in test_positions() {
+            for_each_move(&board, 3, &mut |board, mv, child| {
+                let mut builder = BoardBuilder::from_board(board);
+                let mut played = board.clone();
+                played.play_unchecked_with(mv, |delta| {
+                    let square = builder.square_mut(delta.square);
+                    if delta.added {
+                        assert_eq!(*square, None, "{} {}", board, mv);
+                        *square = Some((delta.piece, delta.color));
+                    } else {
+                        assert_eq!(*square, Some((delta.piece, delta.color)), "{} {}", board, mv);
+                        *square = None;
+                    }
+                });
+                assert_eq!(&played, child, "{} {}", board, mv);
+                assert_eq!(builder.board, BoardBuilder::from_board(child).board, "{} {}", board, mv);
+            });
+        }
+    }
+
     #[test]
     fn status_checkmate_priority_over_50_mr() {
         let board = "8/8/2p5/3b1K1k/4p3/4Pp1R/5P2/8 b - - 100 113"
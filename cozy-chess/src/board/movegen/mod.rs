@@ -70,7 +70,7 @@ impl Board {
         let color = self.side_to_move();
         let our_king = self.king(color);
         let pieces = self.colored_pieces(color, P::PIECE) & mask;
-        let pinned = self.pinned();
+        let pinned = self.pinned;
         let blockers = self.occupied();
         let target_squares = self.target_squares::<IN_CHECK>();
 
@@ -109,7 +109,7 @@ impl Board {
 
         let color = self.side_to_move();
         let pieces = self.colored_pieces(color, PIECE) & mask;
-        let pinned = self.pinned();
+        let pinned = self.pinned;
         let target_squares = self.target_squares::<IN_CHECK>();
 
         for piece in pieces & !pinned {
@@ -134,7 +134,7 @@ impl Board {
         let our_king = self.king(color);
         let pieces = self.colored_pieces(color, PIECE) & mask;
         let their_pieces = self.colors(!color);
-        let pinned = self.pinned();
+        let pinned = self.pinned;
         let blockers = self.occupied();
         let target_squares = self.target_squares::<IN_CHECK>();
 
@@ -241,7 +241,7 @@ impl Board {
         let our_king = self.king(color);
         let back_rank = Rank::First.relative_to(color);
         let blockers = self.occupied() ^ our_king.bitboard();
-        let pinned = self.pinned();
+        let pinned = self.pinned;
         let rook = Square::new(rook, back_rank);
         let blockers = blockers ^ rook.bitboard();
         let king_dest = Square::new(king_dest, back_rank);
@@ -364,6 +364,78 @@ impl Board {
         }
     }
 
+    /// Run `f` on this board with `color` to move. If `color` is not the side to move,
+    /// this uses a copy with the side to move switched, no en passant square, and checkers
+    /// and pins recalculated for `color`'s king.
+    fn with_side_to_move<R>(&self, color: Color, f: impl FnOnce(&Board) -> R) -> R {
+        if color == self.side_to_move() {
+            return f(self);
+        }
+        let mut board = self.clone();
+        board.inner.toggle_side_to_move();
+        board.inner.set_en_passant(None);
+        let (checkers, pinned) = board.calculate_checkers_and_pins(color);
+        board.checkers = checkers;
+        board.pinned = pinned;
+        f(&board)
+    }
+
+    /// Version of [`Board::generate_moves`] that generates the legal moves `color`
+    /// would have if it were their turn.
+    /// For the side not to move, there is no en passant, and moves that capture the
+    /// opposing king are generated if it is in check.
+    /// Unlike [`Board::null_move`], this works even if the side to move is in check.
+    /// # Examples
+    /// ```
+    /// # use cozy_chess::*;
+    /// let mut board = Board::default();
+    /// board.play("e2e4".parse().unwrap());
+    /// let mut white_moves = 0;
+    /// board.generate_moves_as(Color::White, |moves| {
+    ///     white_moves += moves.len();
+    ///     false
+    /// });
+    /// assert_eq!(white_moves, 30);
+    /// ```
+    pub fn generate_moves_as(&self, color: Color, listener: impl FnMut(PieceMoves) -> bool) -> bool {
+        self.generate_moves_for_as(color, BitBoard::FULL, listener)
+    }
+
+    /// Version of [`Board::generate_moves_as`] that
+    /// generates moves for only a subset of pieces.
+    /// # Examples
+    /// ```
+    /// # use cozy_chess::*;
+    /// let board: Board = "4k3/8/8/8/8/8/3r4/R3K3 w - - 0 1".parse().unwrap();
+    /// let rooks = board.pieces(Piece::Rook);
+    /// let mut black_rook_moves = 0;
+    /// board.generate_moves_for_as(Color::Black, rooks, |moves| {
+    ///     black_rook_moves += moves.len();
+    ///     false
+    /// });
+    /// assert_eq!(black_rook_moves, 14);
+    /// ```
+    pub fn generate_moves_for_as(
+        &self, color: Color, mask: BitBoard, listener: impl FnMut(PieceMoves) -> bool
+    ) -> bool {
+        self.with_side_to_move(color, |board| board.generate_moves_for(mask, listener))
+    }
+
+    /// See if a move would be legal for `color` if it were their turn.
+    /// See [`Board::generate_moves_as`] for details.
+    /// # Examples
+    /// ```
+    /// # use cozy_chess::*;
+    /// let mut board = Board::default();
+    /// board.play("e2e4".parse().unwrap());
+    /// assert!(board.is_legal_as(Color::White, "f1c4".parse().unwrap()));
+    /// assert!(!board.is_legal_as(Color::Black, "f1c4".parse().unwrap()));
+    /// assert!(board.is_legal_as(Color::Black, "e7e5".parse().unwrap()));
+    /// ```
+    pub fn is_legal_as(&self, color: Color, mv: Move) -> bool {
+        self.with_side_to_move(color, |board| board.is_legal(mv))
+    }
+
     fn king_is_legal(&self, mv: Move) -> bool {
         if self.checkers.is_empty() {
             let castles = self.castle_rights(self.side_to_move());
@@ -411,7 +483,7 @@ impl Board {
             return self.king_is_legal(mv);
         }
 
-        if self.pinned().has(mv.from) && !get_line_rays(king_sq, mv.from).has(mv.to) {
+        if self.pinned.has(mv.from) && !get_line_rays(king_sq, mv.from).has(mv.to) {
             return false;
         }
 
@@ -1,38 +1,14 @@
 use std::collections::HashSet;
 
 use super::*;
-
-fn perft(board: &Board, depth: u8) -> u64 {
-    let mut nodes = 0;
-    match depth {
-        0 => nodes += 1,
-        1 => {
-            board.generate_moves(|moves| {
-                nodes += moves.len() as u64;
-                false
-            });
-        }
-        _ => {
-            board.generate_moves(|moves| {
-                for mv in moves {
-                    let mut board = board.clone();
-                    board.play_unchecked(mv);
-                    let child_nodes = perft(&board, depth - 1);
-                    nodes += child_nodes;
-                }
-                false
-            });
-        }
-    }
-    nodes
-}
+use crate::test_util::*;
 
 macro_rules! make_perft_test {
     ($($name:ident($board:expr $(,$node:expr)*);)*) => {
         $(#[test]
         fn $name() {
             let board = $board.parse::<Board>()
-                .or_else(|_| Board::from_fen($board, true))
+                .or_else(|_| Board::from_fen($board, FenFormat::Shredder))
                 .unwrap();
             const NODES: &'static [u64] = &[$($node),*];
             for (depth, &nodes) in NODES.iter().enumerate() {
@@ -255,21 +231,21 @@ fn legality_castles_960() {
     test_is_legal(
         Board::from_fen(
             "rq1kr3/p1ppbp1p/bpn3pB/3Np3/3P4/1P1Q1Nn1/P1P1BPPP/R2KR3 w AEae - 3 15",
-            true,
+            FenFormat::Shredder,
         )
         .unwrap(),
     );
     test_is_legal(
         Board::from_fen(
             "rq1kr3/p1ppbp1p/bpn3pB/3Np3/3P4/1P1Q1Nn1/P1P1BPPP/R2KR3 b AEae - 3 15",
-            true,
+            FenFormat::Shredder,
         )
         .unwrap(),
     );
     test_is_legal(
         Board::from_fen(
             "rk2r3/pqppbp1p/bpn3pB/3Npn2/3P4/1P1Q1N2/P1P2PPP/RKRB4 w ACa - 3 15",
-            true,
+            FenFormat::Shredder,
         )
         .unwrap(),
     );
@@ -288,3 +264,64 @@ fn legality_en_passant() {
             .unwrap(),
     );
 }
+
+#[test]
+fn movegen_as_other_side() {
+    fn moves_as(board: &Board, color: Color) -> HashSet<Move> {
+        let mut moves = HashSet::new();
+        board.generate_moves_as(color, |mvs| {
+            moves.extend(mvs);
+            false
+        });
+        moves
+    }
+
+    fn check(board: &Board) {
+        let color = board.side_to_move();
+        let mut legals = HashSet::new();
+        board.generate_moves(|mvs| {
+            legals.extend(mvs);
+            false
+        });
+        assert_eq!(moves_as(board, color), legals);
+        assert_eq!(board.checkers_for(color), board.checkers());
+
+        let theirs = moves_as(board, !color);
+        if let Some(null) = board.null_move() {
+            let mut null_legals = HashSet::new();
+            null.generate_moves(|mvs| {
+                null_legals.extend(mvs);
+                false
+            });
+            assert_eq!(theirs, null_legals);
+            assert_eq!(board.pinned(!color), null.pinned(!color));
+            assert_eq!(board.checkers_for(!color), null.checkers());
+        }
+        for from in Square::ALL {
+            for to in Square::ALL {
+                let mv = Move { from, to, promotion: None };
+                assert_eq!(theirs.contains(&mv), board.is_legal_as(!color, mv), "{}", mv);
+            }
+        }
+
+        // Any checker that isn't pinned to its own king can capture the king in check.
+        let king = board.king(color);
+        for checker in board.checkers() {
+            let captures_king = theirs.iter().any(|mv| mv.from == checker && mv.to == king);
+            assert_eq!(captures_king, !board.pinned(!color).has(checker), "{} {}", board, checker);
+        }
+        assert!(theirs.iter().all(|mv| mv.to != king || board.checkers().has(mv.from)), "{}", board);
+    }
+
+    for board in test_positions() {
+        for_each_position(&board, 1, &mut check);
+    }
+
+    // Black is in check, but white's moves can still be generated, including capturing the king.
+    let board: Board = "3k4/8/8/8/8/8/8/3RK2R b K - 1 2".parse().unwrap();
+    assert!(board.null_move().is_none());
+    let white = moves_as(&board, Color::White);
+    assert!(white.contains(&"d1d8".parse().unwrap()));
+    assert!(white.contains(&"e1h1".parse().unwrap()));
+    assert_eq!(board.checkers_for(Color::White), BitBoard::EMPTY);
+}
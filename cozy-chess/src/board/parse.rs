@@ -6,41 +6,331 @@ use crate::*;
 
 use super::ZobristBoard;
 
-helpers::simple_error! {
-    /// An error while parsing the FEN.
-    pub enum FenParseError {
-        InvalidBoard = "The board is invalid.",
-        InvalidSideToMove = "The side to move is invalid.",
-        InvalidCastlingRights = "The castling rights are invalid.",
-        InvalidEnPassant = "The en passant square is invalid.",
-        InvalidHalfMoveClock = "The halfmove clock is invalid.",
-        InvalidFullmoveNumber = "The fullmove number is invalid.",
-        MissingField = "The FEN is missing a field.",
-        TooManyFields = "The FEN has too many fields."
+/// A field of a FEN string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FenField {
+    /// The piece placement field.
+    Board,
+    /// The side to move field.
+    SideToMove,
+    /// The castle rights field.
+    CastleRights,
+    /// The en passant square field.
+    EnPassant,
+    /// The halfmove clock field.
+    HalfmoveClock,
+    /// The fullmove number field.
+    FullmoveNumber
+}
+
+impl Display for FenField {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let name = match self {
+            Self::Board => "board",
+            Self::SideToMove => "side to move",
+            Self::CastleRights => "castle rights",
+            Self::EnPassant => "en passant square",
+            Self::HalfmoveClock => "halfmove clock",
+            Self::FullmoveNumber => "fullmove number"
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// The reason a FEN string failed to parse. See [`FenParseError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FenParseErrorReason {
+    /// The FEN is missing the field.
+    MissingField,
+    /// The FEN has more fields after the fullmove number.
+    TooManyFields,
+    /// The board has more than eight ranks.
+    TooManyRanks,
+    /// The board has fewer than eight ranks.
+    TooFewRanks,
+    /// A rank has more than eight squares.
+    TooManySquares,
+    /// A rank has fewer than eight squares.
+    TooFewSquares,
+    /// The character is neither a piece nor a count of empty squares.
+    UnknownPiece(char),
+    /// The side has no king.
+    MissingKing(Color),
+    /// The side has more than one king.
+    TooManyKings(Color),
+    /// The side has more than eight pawns.
+    TooManyPawns(Color),
+    /// The side has more than sixteen pieces.
+    TooManyPieces(Color),
+    /// A pawn is on the first or eighth rank.
+    PawnOnBackRank,
+    /// The side that just moved is in check, which is impossible.
+    OpponentInCheck,
+    /// The side to move is in check from more than two pieces.
+    TooManyCheckers,
+    /// The side to move is in a double check that no single move could have given.
+    ImpossibleCheckers,
+    /// The side to move is neither `w` nor `b`.
+    InvalidSideToMove,
+    /// The character is not a castle right in this [`FenFormat`].
+    InvalidCastleRight(char),
+    /// The castle right was given more than once.
+    DuplicateCastleRight,
+    /// The side has a castle right, but its king is not on its back rank.
+    KingNotOnBackRank,
+    /// The castle right has no matching rook on the back rank on that side of the king.
+    CastleRightWithoutRook,
+    /// The en passant square is not a square.
+    InvalidSquare,
+    /// The en passant square is not on the rank a pawn that just moved two squares skipped over.
+    EnPassantWrongRank,
+    /// The en passant square is not behind a pawn that just moved two squares.
+    EnPassantWithoutPawn,
+    /// The en passant square or the square the pawn moved from is occupied.
+    EnPassantSquareOccupied,
+    /// The side to move is in a check that the pawn that just moved could not have given.
+    EnPassantImpossibleCheck,
+    /// The value is not a valid number.
+    InvalidNumber,
+    /// The halfmove clock is greater than 100.
+    HalfmoveClockTooLarge,
+    /// The fullmove number is zero.
+    FullmoveNumberZero
+}
+
+impl Display for FenParseErrorReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        use FenParseErrorReason::*;
+
+        match self {
+            MissingField => write!(f, "the field is missing"),
+            TooManyFields => write!(f, "there are too many fields"),
+            TooManyRanks => write!(f, "there are more than eight ranks"),
+            TooFewRanks => write!(f, "there are fewer than eight ranks"),
+            TooManySquares => write!(f, "the rank has more than eight squares"),
+            TooFewSquares => write!(f, "the rank has fewer than eight squares"),
+            UnknownPiece(c) => write!(f, "'{}' is not a piece", c),
+            MissingKing(color) => write!(f, "{:?} has no king", color),
+            TooManyKings(color) => write!(f, "{:?} has more than one king", color),
+            TooManyPawns(color) => write!(f, "{:?} has more than eight pawns", color),
+            TooManyPieces(color) => write!(f, "{:?} has more than sixteen pieces", color),
+            PawnOnBackRank => write!(f, "a pawn is on the first or eighth rank"),
+            OpponentInCheck => write!(f, "the side that just moved is in check"),
+            TooManyCheckers => write!(f, "the side to move is in check from more than two pieces"),
+            ImpossibleCheckers => write!(f, "the side to move is in an impossible double check"),
+            InvalidSideToMove => write!(f, "the side to move is not 'w' or 'b'"),
+            InvalidCastleRight(c) => write!(f, "'{}' is not a castle right", c),
+            DuplicateCastleRight => write!(f, "the castle right is given more than once"),
+            KingNotOnBackRank => write!(f, "the king is not on its back rank"),
+            CastleRightWithoutRook => write!(f, "there is no rook for the castle right"),
+            InvalidSquare => write!(f, "the value is not a square"),
+            EnPassantWrongRank => write!(f, "the en passant square is on the wrong rank"),
+            EnPassantWithoutPawn => write!(f, "the en passant square is not behind a pawn that just moved"),
+            EnPassantSquareOccupied => write!(f, "the en passant square or the square the pawn moved from is occupied"),
+            EnPassantImpossibleCheck => write!(f, "the pawn that just moved could not have given the check"),
+            InvalidNumber => write!(f, "the value is not a valid number"),
+            HalfmoveClockTooLarge => write!(f, "the halfmove clock is greater than 100"),
+            FullmoveNumberZero => write!(f, "the fullmove number is zero")
+        }
+    }
+}
+
+/// An error while parsing the FEN.
+/// # Examples
+/// ```
+/// # use cozy_chess::*;
+/// let error = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNX w KQkq - 0 1"
+///     .parse::<Board>()
+///     .unwrap_err();
+/// assert_eq!(error.field, FenField::Board);
+/// assert_eq!(error.offset, 42);
+/// assert_eq!(error.reason, FenParseErrorReason::UnknownPiece('X'));
+/// assert_eq!(format!("{}", error), "Invalid board at byte 42: 'X' is not a piece.");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FenParseError {
+    /// The field the error occurred in.
+    pub field: FenField,
+    /// The byte offset in the FEN string where the error occurred.
+    pub offset: usize,
+    /// The reason the FEN is invalid.
+    pub reason: FenParseErrorReason
+}
+
+impl Display for FenParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Invalid {} at byte {}: {}.", self.field, self.offset, self.reason)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FenParseError {}
+
+/// An error reason with its byte offset relative to the start of the field.
+type FieldError = (usize, FenParseErrorReason);
+
+/// The reason for a violation reported by the board validators.
+fn violation_reason(error: BoardBuilderError) -> FenParseErrorReason {
+    use BoardBuilderError::*;
+
+    match error {
+        MissingKing(color) => FenParseErrorReason::MissingKing(color),
+        TooManyKings(color) => FenParseErrorReason::TooManyKings(color),
+        TooManyPawns(color) => FenParseErrorReason::TooManyPawns(color),
+        TooManyPieces(color) => FenParseErrorReason::TooManyPieces(color),
+        PawnOnBackRank(_) => FenParseErrorReason::PawnOnBackRank,
+        OpponentInCheck => FenParseErrorReason::OpponentInCheck,
+        TooManyCheckers => FenParseErrorReason::TooManyCheckers,
+        ImpossibleCheckers => FenParseErrorReason::ImpossibleCheckers,
+        KingNotOnBackRank(_) => FenParseErrorReason::KingNotOnBackRank,
+        CastleRightWithoutRook(_, _) => FenParseErrorReason::CastleRightWithoutRook,
+        EnPassantWrongRank => FenParseErrorReason::EnPassantWrongRank,
+        EnPassantWithoutPawn => FenParseErrorReason::EnPassantWithoutPawn,
+        EnPassantSquareOccupied => FenParseErrorReason::EnPassantSquareOccupied,
+        EnPassantImpossibleCheck => FenParseErrorReason::EnPassantImpossibleCheck,
+        HalfmoveClockTooLarge => FenParseErrorReason::HalfmoveClockTooLarge,
+        FullmoveNumberZero => FenParseErrorReason::FullmoveNumberZero
+    }
+}
+
+/// A FEN dialect. The dialects differ only in how castle rights are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FenFormat {
+    /// Standard FEN. Castle rights are written as `KQkq`, which always refer to rooks
+    /// on the H and A files. This cannot represent most chess960 castle rights.
+    Standard,
+    /// Shredder FEN. Castle rights are written as the files of the castling rooks, such as `HAha`.
+    Shredder,
+    /// X-FEN. Castle rights are written as `KQkq` if the castling rook is the outermost rook
+    /// on that side of the king, and as the file of the rook otherwise.
+    /// This is identical to standard FEN for standard chess positions.
+    /// When parsing, both notations are accepted, so Shredder FEN also parses as X-FEN.
+    XFen
+}
+
+/// Options for parsing a FEN string. See [`Board::from_fen_with`].
+///
+/// The leniency options accept common mistakes found in real-world FENs
+/// and repair them instead of failing. Each repair is reported as a [`FenFix`].
+/// # Examples
+/// ```
+/// # use cozy_chess::*;
+/// let options = FenOptions::lenient(FenFormat::XFen);
+/// assert!(options.lenient_whitespace);
+/// assert_eq!(FenOptions::default(), FenOptions::strict(FenFormat::XFen));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FenOptions {
+    /// The FEN dialect to parse.
+    pub format: FenFormat,
+    /// Accept any amount of whitespace between and around fields.
+    pub lenient_whitespace: bool,
+    /// Default missing clocks or clocks given as `-` to a halfmove clock of 0 and a fullmove number of 1.
+    pub default_clocks: bool,
+    /// Remove an en passant square that is invalid or on which no en passant capture is legal.
+    pub normalize_en_passant: bool,
+    /// Remove castle rights whose king is not on its back rank or that have no matching rook.
+    pub strip_castle_rights: bool
+}
+
+impl FenOptions {
+    /// Options that accept only well-formed FENs. This is what [`Board::from_fen`] uses.
+    pub const fn strict(format: FenFormat) -> Self {
+        Self {
+            format,
+            lenient_whitespace: false,
+            default_clocks: false,
+            normalize_en_passant: false,
+            strip_castle_rights: false
+        }
+    }
+
+    /// Options that enable every repair.
+    pub const fn lenient(format: FenFormat) -> Self {
+        Self {
+            format,
+            lenient_whitespace: true,
+            default_clocks: true,
+            normalize_en_passant: true,
+            strip_castle_rights: true
+        }
     }
 }
 
+impl Default for FenOptions {
+    fn default() -> Self {
+        Self::strict(FenFormat::XFen)
+    }
+}
+
+/// A repair made while leniently parsing a FEN. See [`FenOptions`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FenFix {
+    /// Extra whitespace was ignored.
+    NormalizedWhitespace,
+    /// The halfmove clock was missing or `-` and was set to 0.
+    DefaultedHalfmoveClock,
+    /// The fullmove number was missing or `-` and was set to 1.
+    DefaultedFullmoveNumber,
+    /// The en passant square was removed since it was invalid or no en passant capture was legal.
+    RemovedEnPassant(Square),
+    /// The castle right, given as its FEN character, was removed since it was impossible.
+    RemovedCastleRight(char)
+}
+
 impl Board {
-    /// Parse a FEN string. If `shredder` is true, it parses Shredder FEN instead.
-    /// You can also parse the board with [`FromStr`], which parses both FEN types.
+    /// Parse a FEN string in some [`FenFormat`].
+    /// You can also parse the board with [`FromStr`], which parses X-FEN and therefore all three formats.
     /// # Examples
     /// ## FEN
     /// ```
     /// # use cozy_chess::*;
     /// const STARTPOS: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
-    /// let board = Board::from_fen(STARTPOS, false).unwrap();
+    /// let board = Board::from_fen(STARTPOS, FenFormat::Standard).unwrap();
     /// assert_eq!(format!("{}", board), STARTPOS);
     /// ```
     /// ## Shredder FEN
     /// ```
     /// # use cozy_chess::*;
     /// const STARTPOS: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w HAha - 0 1";
-    /// let board = Board::from_fen(STARTPOS, true).unwrap();
+    /// let board = Board::from_fen(STARTPOS, FenFormat::Shredder).unwrap();
     /// assert_eq!(format!("{:#}", board), STARTPOS);
     /// ```
-    pub fn from_fen(fen: &str, shredder: bool) -> Result<Self, FenParseError> {
-        use FenParseError::*;
+    /// ## X-FEN
+    /// ```
+    /// # use cozy_chess::*;
+    /// // The white rook on B1 is not the outermost rook on the queenside.
+    /// const FEN: &str = "rk4r1/pppppppp/8/8/8/8/PPPPPPPP/RRK4R w BHag - 0 1";
+    /// let board = Board::from_fen(FEN, FenFormat::XFen).unwrap();
+    /// assert_eq!(board.castle_rights(Color::White).long, Some(File::B));
+    /// assert_eq!(board.castle_rights(Color::Black).short, Some(File::G));
+    /// assert_eq!(format!("{}", board.display_fen(FenFormat::XFen)), "rk4r1/pppppppp/8/8/8/8/PPPPPPPP/RRK4R w KBkq - 0 1");
+    /// ```
+    pub fn from_fen(fen: &str, format: FenFormat) -> Result<Self, FenParseError> {
+        Self::from_fen_with(fen, FenOptions::strict(format), |_| {})
+    }
 
+    /// Parse a FEN string with some [`FenOptions`].
+    /// The `listener` receives a [`FenFix`] for each repair made. If parsing fails,
+    /// the fixes reported before the error should be discarded.
+    /// Error offsets always refer to the original string.
+    /// # Examples
+    /// ```
+    /// # use cozy_chess::*;
+    /// let fen = "  rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR  b KQkq e3";
+    /// let mut fixes = Vec::new();
+    /// let options = FenOptions::lenient(FenFormat::XFen);
+    /// let board = Board::from_fen_with(fen, options, |fix| fixes.push(fix)).unwrap();
+    /// assert_eq!(fixes, [
+    ///     FenFix::NormalizedWhitespace,
+    ///     FenFix::RemovedEnPassant(Square::E3),
+    ///     FenFix::DefaultedHalfmoveClock,
+    ///     FenFix::DefaultedFullmoveNumber
+    /// ]);
+    /// assert_eq!(format!("{}", board), "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1");
+    /// ```
+    pub fn from_fen_with(fen: &str, options: FenOptions, mut listener: impl FnMut(FenFix)) -> Result<Self, FenParseError> {
+        let format = options.format;
         let mut board = Self {
             inner: ZobristBoard::empty(),
             pinned: BitBoard::EMPTY,
@@ -48,150 +338,352 @@ impl Board {
             halfmove_clock: 0,
             fullmove_number: 0
         };
-        let mut parts = fen.split(' ');
-        let mut next = || parts.next().ok_or(MissingField);
-        
-        Self::parse_board(&mut board, next()?)
-            .map_err(|_| InvalidBoard)?;
-        Self::parse_side_to_move(&mut board, next()?)
-            .map_err(|_| InvalidSideToMove)?;
-        if !board.board_is_valid() {
-            return Err(InvalidBoard);
+        let mut split = fen.split(' ');
+        let mut split_whitespace = fen.split_whitespace();
+        let parts: &mut dyn Iterator<Item = &str> = if options.lenient_whitespace {
+            if fen.split(' ').ne(fen.split_whitespace()) {
+                listener(FenFix::NormalizedWhitespace);
+            }
+            &mut split_whitespace
+        } else {
+            &mut split
+        };
+        // Every part is a subslice of the FEN, so this recovers its offset in the original string.
+        let mut parts = parts.map(|part| (part.as_ptr() as usize - fen.as_ptr() as usize, part));
+        // Optional fields are parsed as "-" when missing.
+        let mut parse_field = |field, optional: bool, parse: &mut dyn FnMut(&str) -> Result<(), FieldError>| {
+            let (offset, part) = match parts.next() {
+                Some(part) => part,
+                None if optional => (fen.len(), "-"),
+                None => return Err(FenParseError {
+                    field,
+                    offset: fen.len(),
+                    reason: FenParseErrorReason::MissingField
+                })
+            };
+            parse(part).map_err(|(relative, reason)| FenParseError {
+                field,
+                offset: offset + relative,
+                reason
+            })?;
+            Ok(offset)
+        };
+
+        let mut square_offsets = [0; Square::NUM];
+        parse_field(FenField::Board, false, &mut |s| board.parse_board(s, &mut square_offsets))?;
+        let side_to_move_offset = parse_field(FenField::SideToMove, false, &mut |s| board.parse_side_to_move(s))?;
+        if let Some((offset, reason)) = board.first_piece_error(&square_offsets) {
+            return Err(FenParseError {
+                field: FenField::Board,
+                offset,
+                reason
+            });
         }
 
+        let mut opponent_in_check = false;
+        board.validate_opponent_check(&mut |_| opponent_in_check = true);
+        if opponent_in_check {
+            return Err(FenParseError {
+                field: FenField::SideToMove,
+                offset: side_to_move_offset,
+                reason: FenParseErrorReason::OpponentInCheck
+            });
+        }
         let (checkers, pinned) = board.calculate_checkers_and_pins(board.side_to_move());
         board.checkers = checkers;
         board.pinned = pinned;
-        if !board.checkers_and_pins_are_valid() {
-            return Err(InvalidBoard);
-        }
-
-        Self::parse_castle_rights(&mut board, next()?, shredder)
-            .map_err(|_| InvalidCastlingRights)?;
-        if !board.castle_rights_are_valid() {
-            return Err(InvalidCastlingRights);
-        }
-
-        Self::parse_en_passant(&mut board, next()?)
-            .map_err(|_| InvalidEnPassant)?;
-        if !board.en_passant_is_valid() {
-            return Err(InvalidEnPassant);
-        }
-
-        Self::parse_halfmove_clock(&mut board, next()?)
-            .map_err(|_| InvalidHalfMoveClock)?;
-        if !board.halfmove_clock_is_valid() {
-            return Err(InvalidHalfMoveClock);
+        let mut checkers_reason = None;
+        board.validate_checkers(&mut |error| checkers_reason = Some(violation_reason(error)));
+        if let Some(reason) = checkers_reason {
+            let checker = checkers.into_iter().last().unwrap();
+            return Err(FenParseError {
+                field: FenField::Board,
+                offset: square_offsets[checker as usize],
+                reason
+            });
         }
+        debug_assert!(board.board_is_valid() && board.checkers_and_pins_are_valid());
 
-        Self::parse_fullmove_number(&mut board, next()?)
-            .map_err(|_| InvalidFullmoveNumber)?;
-        if !board.fullmove_number_is_valid() {
-            return Err(InvalidFullmoveNumber);
-        }
+        parse_field(FenField::CastleRights, false, &mut |s| {
+            board.parse_castle_rights(s, format, options.strip_castle_rights, &mut listener)
+        })?;
+        debug_assert!(board.castle_rights_are_valid());
+        parse_field(FenField::EnPassant, false, &mut |s| {
+            match board.parse_en_passant(s) {
+                Err((_, reason)) if options.normalize_en_passant && reason != FenParseErrorReason::InvalidSquare => {
+                    listener(FenFix::RemovedEnPassant(s.parse().unwrap()));
+                }
+                result => result?
+            }
+            if options.normalize_en_passant && board.en_passant().is_some() && board.effective_en_passant().is_none() {
+                board.inner.set_en_passant(None);
+                listener(FenFix::RemovedEnPassant(s.parse().unwrap()));
+            }
+            Ok(())
+        })?;
+        debug_assert!(board.en_passant_is_valid());
+        parse_field(FenField::HalfmoveClock, options.default_clocks, &mut |s| {
+            if options.default_clocks && s == "-" {
+                board.halfmove_clock = 0;
+                listener(FenFix::DefaultedHalfmoveClock);
+                return Ok(());
+            }
+            board.parse_halfmove_clock(s)
+        })?;
+        parse_field(FenField::FullmoveNumber, options.default_clocks, &mut |s| {
+            if options.default_clocks && s == "-" {
+                board.fullmove_number = 1;
+                listener(FenFix::DefaultedFullmoveNumber);
+                return Ok(());
+            }
+            board.parse_fullmove_number(s)
+        })?;
 
-        if parts.next().is_some() {
-            return Err(TooManyFields);
+        if let Some((offset, _)) = parts.next() {
+            return Err(FenParseError {
+                field: FenField::FullmoveNumber,
+                offset,
+                reason: FenParseErrorReason::TooManyFields
+            });
         }
 
         Ok(board)
     }
 
-    fn parse_board(board: &mut Board, s: &str) -> Result<(), ()> {
-        for (rank, row) in s.rsplit('/').enumerate() {
-            let rank = Rank::try_index(rank).ok_or(())?;
+    fn parse_board(&mut self, s: &str, square_offsets: &mut [usize; Square::NUM]) -> Result<(), FieldError> {
+        use FenParseErrorReason::*;
+
+        let mut rank_offset = 0;
+        let mut ranks = 0;
+        for (index, row) in s.split('/').enumerate() {
+            if index >= Rank::NUM {
+                return Err((rank_offset, TooManyRanks));
+            }
+            let rank = Rank::index(Rank::NUM - 1 - index);
             let mut file = 0;
-            for p in row.chars() {
-                if let Some(offset) = p.to_digit(10) {
-                    file += offset as usize;
+            for (offset, p) in row.char_indices() {
+                let offset = rank_offset + offset;
+                if file >= File::NUM {
+                    return Err((offset, TooManySquares));
+                }
+                if let Some(empty) = p.to_digit(10) {
+                    file += empty as usize;
+                    if file > File::NUM {
+                        return Err((offset, TooManySquares));
+                    }
                 } else {
-                    let piece = p.to_ascii_lowercase().try_into().map_err(|_| ())?;
+                    let piece = p.to_ascii_lowercase().try_into()
+                        .map_err(|_| (offset, UnknownPiece(p)))?;
                     let color = if p.is_ascii_uppercase() {
                         Color::White
                     } else {
                         Color::Black
                     };
-                    let square = Square::new(
-                        File::try_index(file).ok_or(())?,
-                        rank
-                    );
-                    board.inner.xor_square(piece, color, square);
+                    let square = Square::new(File::index(file), rank);
+                    self.inner.xor_square(piece, color, square);
+                    square_offsets[square as usize] = offset;
                     file += 1;
                 }
             }
-            if file != File::NUM {
-                return Err(());
+            rank_offset += row.len();
+            if file < File::NUM {
+                return Err((rank_offset, TooFewSquares));
             }
+            rank_offset += 1;
+            ranks += 1;
+        }
+        if ranks < Rank::NUM {
+            return Err((s.len(), TooFewRanks));
         }
         Ok(())
     }
 
-    fn parse_side_to_move(board: &mut Board, s: &str) -> Result<(), ()> {
-        if s.parse::<Color>().map_err(|_| ())? != board.side_to_move() {
-            board.inner.toggle_side_to_move();
+    fn parse_side_to_move(&mut self, s: &str) -> Result<(), FieldError> {
+        let color = s.parse::<Color>().map_err(|_| (0, FenParseErrorReason::InvalidSideToMove))?;
+        if color != self.side_to_move() {
+            self.inner.toggle_side_to_move();
         }
         Ok(())
     }
 
-    fn parse_castle_rights(board: &mut Board, s: &str, shredder: bool) -> Result<(), ()> {
-        if s != "-" {
-            for c in s.chars() {
-                let color = if c.is_ascii_uppercase() {
-                    Color::White
-                } else {
-                    Color::Black
-                };
-                let king_file = board.king(color).file();
-                let (short, file) = if shredder {
-                    let file = c.to_ascii_lowercase().try_into().map_err(|_| ())?;
-                    (king_file < file, file)
-                } else {
-                    match c.to_ascii_lowercase() {
-                        'k' => (true, File::H),
-                        'q' => (false, File::A),
-                        _ => return Err(())
-                    }
-                };
-                let rights = board.castle_rights(color);
-                let prev = if short {
-                    rights.short
-                } else {
-                    rights.long
-                };
-                if prev.is_some() {
-                    // Duplicates
-                    return Err(());
+    /// Find the [`Board::validate_pieces`] violation closest to the start of the board field.
+    /// Violations point at the first piece over a limit, and a missing king is reported last.
+    fn first_piece_error(&self, square_offsets: &[usize; Square::NUM]) -> Option<FieldError> {
+        // The offset of the piece at `index` in FEN order.
+        let offset_of = |pieces: BitBoard, index: usize| {
+            let square = Rank::ALL.iter().rev()
+                .flat_map(|&rank| File::ALL.iter().map(move |&file| Square::new(file, rank)))
+                .filter(|&square| pieces.has(square))
+                .nth(index)
+                .unwrap();
+            square_offsets[square as usize]
+        };
+        let mut first = None;
+        self.validate_pieces(&mut |error| {
+            use BoardBuilderError::*;
+
+            let offset = match error {
+                MissingKing(_) => 0,
+                TooManyKings(color) => offset_of(self.colored_pieces(color, Piece::King), 1),
+                TooManyPawns(color) => offset_of(self.colored_pieces(color, Piece::Pawn), 8),
+                TooManyPieces(color) => offset_of(self.colors(color), 16),
+                PawnOnBackRank(square) => square_offsets[square as usize],
+                _ => unreachable!()
+            };
+            let key = (matches!(error, MissingKing(_)), offset);
+            match first {
+                Some((first_key, _)) if first_key <= key => {}
+                _ => first = Some((key, (offset, violation_reason(error))))
+            }
+        });
+        first.map(|(_, error)| error)
+    }
+
+    fn parse_castle_rights(
+        &mut self,
+        s: &str,
+        format: FenFormat,
+        strip_impossible: bool,
+        listener: &mut dyn FnMut(FenFix)
+    ) -> Result<(), FieldError> {
+        use FenParseErrorReason::*;
+
+        if s == "-" {
+            return Ok(());
+        }
+        for (offset, c) in s.char_indices() {
+            match self.parse_castle_right(c, format) {
+                Err(KingNotOnBackRank | CastleRightWithoutRook) if strip_impossible => {
+                    listener(FenFix::RemovedCastleRight(c));
                 }
-                board.inner.set_castle_right(color, short, Some(file));
+                result => result.map_err(|reason| (offset, reason))?
             }
         }
         Ok(())
     }
 
-    fn parse_en_passant(board: &mut Board, s: &str) -> Result<(), ()> {
-        if s != "-" {
-            let square = s.parse::<Square>().map_err(|_| ())?;
-            let en_passant_rank = Rank::Third.relative_to(!board.side_to_move());
-            if square.rank() != en_passant_rank {
-                return Err(());
+    fn parse_castle_right(&mut self, c: char, format: FenFormat) -> Result<(), FenParseErrorReason> {
+        use FenParseErrorReason::*;
+
+        let color = if c.is_ascii_uppercase() {
+            Color::White
+        } else {
+            Color::Black
+        };
+        let king = self.king(color);
+        let back_rank = Rank::First.relative_to(color);
+        let (short, file) = match (format, c.to_ascii_lowercase()) {
+            (FenFormat::Standard, 'k') => (true, File::H),
+            (FenFormat::Standard, 'q') => (false, File::A),
+            (FenFormat::XFen, 'k') => {
+                let file = self.outermost_rook(color, true).ok_or(CastleRightWithoutRook)?;
+                (true, file)
             }
-            board.inner.set_en_passant(Some(square.file()));
+            (FenFormat::XFen, 'q') => {
+                let file = self.outermost_rook(color, false).ok_or(CastleRightWithoutRook)?;
+                (false, file)
+            }
+            (FenFormat::Shredder | FenFormat::XFen, lower) => {
+                let file: File = lower.try_into().map_err(|_| InvalidCastleRight(c))?;
+                (king.file() < file, file)
+            }
+            _ => return Err(InvalidCastleRight(c))
+        };
+        if king.rank() != back_rank {
+            return Err(KingNotOnBackRank);
+        }
+        let rook = Square::new(file, back_rank);
+        let on_side = if short {
+            king.file() < file
+        } else {
+            file < king.file()
+        };
+        if !self.colored_pieces(color, Piece::Rook).has(rook) || !on_side {
+            return Err(CastleRightWithoutRook);
+        }
+        let rights = self.castle_rights(color);
+        let prev = if short {
+            rights.short
+        } else {
+            rights.long
+        };
+        if prev.is_some() {
+            return Err(DuplicateCastleRight);
+        }
+        self.inner.set_castle_right(color, short, Some(file));
+        Ok(())
+    }
+
+    fn outermost_rook(&self, color: Color, short: bool) -> Option<File> {
+        let back_rank = Rank::First.relative_to(color);
+        let king_file = self.king(color).file();
+        let rooks = (self.colored_pieces(color, Piece::Rook) & back_rank.bitboard())
+            .into_iter()
+            .map(|square| square.file());
+        if short {
+            rooks.filter(|&file| file > king_file).max()
+        } else {
+            rooks.filter(|&file| file < king_file).min()
+        }
+    }
+
+    /// Display the board as a FEN string in some [`FenFormat`].
+    /// The [`Display`] implementation for [`Board`] uses X-FEN by default and Shredder FEN
+    /// in the alternate mode.
+    /// # Examples
+    /// ```
+    /// # use cozy_chess::*;
+    /// let board = Board::default();
+    /// let fen = format!("{}", board.display_fen(FenFormat::Shredder));
+    /// assert_eq!(fen, "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w HAha - 0 1");
+    /// ```
+    pub fn display_fen(&self, format: FenFormat) -> impl Display + '_ {
+        struct FenDisplay<'b>(&'b Board, FenFormat);
+
+        impl Display for FenDisplay<'_> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+                self.0.write_fen(f, self.1)
+            }
+        }
+
+        FenDisplay(self, format)
+    }
+
+    fn parse_en_passant(&mut self, s: &str) -> Result<(), FieldError> {
+        use FenParseErrorReason::*;
+
+        if s == "-" {
+            return Ok(());
+        }
+        let square = s.parse::<Square>().map_err(|_| (0, InvalidSquare))?;
+        let color = self.side_to_move();
+        if square.rank() != Rank::Third.relative_to(!color) {
+            return Err((0, EnPassantWrongRank));
+        }
+        self.inner.set_en_passant(Some(square.file()));
+        let mut reason = None;
+        self.validate_en_passant(&mut |error| {
+            reason = reason.or(Some(violation_reason(error)));
+        });
+        if let Some(reason) = reason {
+            self.inner.set_en_passant(None);
+            return Err((0, reason));
         }
         Ok(())
     }
 
-    fn parse_halfmove_clock(board: &mut Board, s: &str) -> Result<(), ()> {
-        board.halfmove_clock = s.parse().map_err(|_| ())?;
-        if board.halfmove_clock > 100 {
-            return Err(());
+    fn parse_halfmove_clock(&mut self, s: &str) -> Result<(), FieldError> {
+        self.halfmove_clock = s.parse().map_err(|_| (0, FenParseErrorReason::InvalidNumber))?;
+        if !self.halfmove_clock_is_valid() {
+            return Err((0, FenParseErrorReason::HalfmoveClockTooLarge));
         }
         Ok(())
     }
 
-    fn parse_fullmove_number(board: &mut Board, s: &str) -> Result<(), ()> {
-        board.fullmove_number = s.parse().map_err(|_| ())?;
-        if board.fullmove_number == 0 {
-            return Err(());
+    fn parse_fullmove_number(&mut self, s: &str) -> Result<(), FieldError> {
+        self.fullmove_number = s.parse().map_err(|_| (0, FenParseErrorReason::InvalidNumber))?;
+        if !self.fullmove_number_is_valid() {
+            return Err((0, FenParseErrorReason::FullmoveNumberZero));
         }
         Ok(())
     }
@@ -201,7 +693,7 @@ impl FromStr for Board {
     type Err = FenParseError;
 
     /// Parse the board.
-    /// This method will parse both regular FENs and Shredder FENs.
+    /// This parses X-FEN, which accepts regular FENs, X-FENs, and Shredder FENs.
     /// See also: [`Board::from_fen`].
     /// # Examples
     /// ```
@@ -211,16 +703,14 @@ impl FromStr for Board {
     /// assert_eq!(format!("{}", board), STARTPOS);
     /// ```
     fn from_str(fen: &str) -> Result<Self, Self::Err> {
-        match Self::from_fen(fen, false) {
-            Ok(board) => Ok(board),
-            Err(FenParseError::InvalidCastlingRights) => Self::from_fen(fen, true),
-            Err(error) => Err(error)
-        }
+        Self::from_fen(fen, FenFormat::XFen)
     }
 }
 
 impl Display for Board {
-    /// Display the board. You can use the alternate format mode for Shredder FEN.
+    /// Display the board as an X-FEN string, which is identical to a regular FEN for standard chess.
+    /// You can use the alternate format mode for Shredder FEN.
+    /// See also: [`Board::display_fen`].
     /// # Examples
     /// ## FEN
     /// ```
@@ -237,7 +727,17 @@ impl Display for Board {
     /// assert_eq!(format!("{:#}", board), STARTPOS);
     /// ```
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        let shredder = f.alternate();
+        let format = if f.alternate() {
+            FenFormat::Shredder
+        } else {
+            FenFormat::XFen
+        };
+        self.write_fen(f, format)
+    }
+}
+
+impl Board {
+    fn write_fen(&self, f: &mut Formatter<'_>, format: FenFormat) -> core::fmt::Result {
         for &rank in Rank::ALL.iter().rev() {
             let mut empty = 0;
             for &file in &File::ALL {
@@ -267,16 +767,17 @@ impl Display for Board {
         let mut wrote_castle_rights = false;
         for &color in &Color::ALL {
             let rights = self.castle_rights(color);
-            let short = rights.short.map(|file| if shredder {
-                file.into()
-            } else {
-                'k'
-            });
-            let long = rights.long.map(|file| if shredder {
-                file.into()
-            } else {
-                'q'
-            });
+            let write_right = |file: File, short: bool, letter: char| match format {
+                FenFormat::Standard => letter,
+                FenFormat::Shredder => file.into(),
+                FenFormat::XFen => if self.outermost_rook(color, short) == Some(file) {
+                    letter
+                } else {
+                    file.into()
+                }
+            };
+            let short = rights.short.map(|file| write_right(file, true, 'k'));
+            let long = rights.long.map(|file| write_right(file, false, 'q'));
             for mut right in short.into_iter().chain(long) {
                 if color == Color::White {
                     right = right.to_ascii_uppercase();
@@ -306,21 +807,187 @@ mod tests {
     #[test]
     fn handles_valid_fens() {
         for fen in include_str!("test_data/valid.sfens").lines() {
-            let board = Board::from_fen(fen, true).unwrap();
+            let board = Board::from_fen(fen, FenFormat::Shredder).unwrap();
             assert!(board.validity_check());
         }
     }
 
     #[test]
     fn handles_invalid_fens() {
+        use FenParseErrorReason::*;
+
+        let mut reasons = Vec::new();
         for fen in include_str!("test_data/invalid.sfens").lines() {
-            assert!(Board::from_fen(fen, true).is_err(), "FEN \"{}\" should not parse", fen);
+            let error = Board::from_fen(fen, FenFormat::Shredder).expect_err(fen);
+            reasons.push(core::mem::discriminant(&error.reason));
+        }
+        let all_reasons = [
+            MissingField, TooManyFields, TooManyRanks, TooFewRanks, TooManySquares, TooFewSquares,
+            UnknownPiece('X'), MissingKing(Color::White), TooManyKings(Color::White),
+            TooManyPawns(Color::White), TooManyPieces(Color::White), PawnOnBackRank, OpponentInCheck,
+            TooManyCheckers, ImpossibleCheckers, InvalidSideToMove, InvalidCastleRight('K'), DuplicateCastleRight,
+            KingNotOnBackRank, CastleRightWithoutRook, InvalidSquare, EnPassantWrongRank,
+            EnPassantWithoutPawn, EnPassantSquareOccupied, EnPassantImpossibleCheck, InvalidNumber,
+            HalfmoveClockTooLarge, FullmoveNumberZero
+        ];
+        for reason in &all_reasons {
+            assert!(reasons.contains(&core::mem::discriminant(reason)), "{:?} is not covered", reason);
+        }
+    }
+
+    #[test]
+    fn invalid_fen_errors() {
+        use FenParseErrorReason::*;
+
+        let cases = [
+            ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w HAha - 0", FenField::FullmoveNumber, 54, MissingField),
+            ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w HAha - 0 1 extra", FenField::FullmoveNumber, 57, TooManyFields),
+            ("rnbqkbnr/pppppppp/8/8/8/8/8/PPPPPPPP/RNBQKBNR w HAha - 0 1", FenField::Board, 37, TooManyRanks),
+            ("rnbqkbnr/pppppppp/8/8/8/PPPPPPPP/RNBQKBNR w HAha - 0 1", FenField::Board, 41, TooFewRanks),
+            ("rnbqkbnr/pppppppp/8/8/44p/8/PPPPPPPP/RNBQKBNR w HAha - 0 1", FenField::Board, 24, TooManySquares),
+            ("rnbqkbnr/ppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w HAha - 0 1", FenField::Board, 16, TooFewSquares),
+            ("rnbqkbnr/pppppppp/8/8/4X3/8/PPPPPPPP/RNBQKBNR w HAha - 0 1", FenField::Board, 23, UnknownPiece('X')),
+            ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR x HAha - 0 1", FenField::SideToMove, 44, InvalidSideToMove),
+            ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", FenField::CastleRights, 46, InvalidCastleRight('K')),
+            ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w HHa - 0 1", FenField::CastleRights, 47, DuplicateCastleRight),
+            ("rnbq1bnr/ppppkppp/8/8/8/8/PPPPPPPP/RNBQKBNR w HAha - 0 1", FenField::CastleRights, 48, KingNotOnBackRank),
+            ("rnbqkbn1/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w HAha - 0 1", FenField::CastleRights, 48, CastleRightWithoutRook),
+            ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w HAha i6 0 1", FenField::EnPassant, 51, InvalidSquare),
+            ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w HAha e3 0 1", FenField::EnPassant, 51, EnPassantWrongRank),
+            ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR b HAha e3 0 1", FenField::EnPassant, 51, EnPassantWithoutPawn),
+            ("rnbqkbnr/pppppppp/8/8/4P3/4N3/PPPP1PPP/RNBQKB1R b - e3 0 1", FenField::EnPassant, 52, EnPassantSquareOccupied),
+            ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w HAha - x 1", FenField::HalfmoveClock, 53, InvalidNumber),
+            ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w HAha - 101 1", FenField::HalfmoveClock, 53, HalfmoveClockTooLarge),
+            ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w HAha - 0 -1", FenField::FullmoveNumber, 55, InvalidNumber),
+            ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w HAha - 0 0", FenField::FullmoveNumber, 55, FullmoveNumberZero),
+            ("rnbqkbnp/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w - - 0 1", FenField::Board, 7, PawnOnBackRank),
+            ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBKKBNR w - - 0 1", FenField::Board, 39, TooManyKings(Color::White)),
+            ("rnbqkbnr/pppppppp/8/8/8/P7/PPPPPPPP/RNBQKBNR w - - 0 1", FenField::Board, 34, TooManyPawns(Color::White)),
+            ("rnbqkbnr/pppppppp/8/8/8/NN6/PPPPPPPP/RNBQKBNR w - - 0 1", FenField::Board, 43, TooManyPieces(Color::White)),
+            ("4k3/8/8/8/8/8/8/8 w - - 0 1", FenField::Board, 0, MissingKing(Color::White)),
+            ("4k3/8/8/8/8/8/8/4R1K1 w - - 0 1", FenField::SideToMove, 22, OpponentInCheck),
+            ("4k3/8/3N1N2/8/8/8/8/4K3 b - - 0 1", FenField::Board, 9, ImpossibleCheckers)
+        ];
+        for &(fen, field, offset, reason) in &cases {
+            let error = Board::from_fen(fen, FenFormat::Shredder).unwrap_err();
+            assert_eq!(error, FenParseError { field, offset, reason }, "{}", fen);
+        }
+    }
+
+    #[test]
+    fn xfen_roundtrip() {
+        let fens = include_str!("test_data/valid.sfens").lines()
+            .chain(include_str!("test_data/chess960_start_positions.sfens").lines());
+        for fen in fens {
+            let board = Board::from_fen(fen, FenFormat::Shredder).unwrap();
+            let xfen = format!("{}", board.display_fen(FenFormat::XFen));
+            assert_eq!(Board::from_fen(&xfen, FenFormat::XFen).unwrap(), board, "{}", xfen);
+            assert_eq!(Board::from_fen(fen, FenFormat::XFen).unwrap(), board);
+            assert_eq!(format!("{}", board), xfen);
+            if let Ok(standard) = Board::from_fen(&xfen, FenFormat::Standard) {
+                assert_eq!(standard, board);
+            }
+        }
+    }
+
+    #[test]
+    fn xfen_uses_outermost_rook() {
+        let fen = "4k3/8/8/8/8/8/8/RR2K1RR w KQ - 0 1";
+        let board = Board::from_fen(fen, FenFormat::XFen).unwrap();
+        assert_eq!(board.castle_rights(Color::White).short, Some(File::H));
+        assert_eq!(board.castle_rights(Color::White).long, Some(File::A));
+        assert!(Board::from_fen("4k3/8/8/8/8/8/8/1R2K1R1 w KQ - 0 1", FenFormat::Standard).is_err());
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/1R2K1R1 w KQ - 0 1", FenFormat::XFen).unwrap();
+        assert_eq!(board.castle_rights(Color::White).short, Some(File::G));
+        assert_eq!(board.castle_rights(Color::White).long, Some(File::B));
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/RR2K1RR w BG - 0 1", FenFormat::XFen).unwrap();
+        assert_eq!(format!("{}", board), "4k3/8/8/8/8/8/8/RR2K1RR w GB - 0 1");
+    }
+
+    #[test]
+    fn lenient_fens() {
+        use FenFix::*;
+
+        let cases: &[(&str, &str, &[FenFix])] = &[
+            (
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -",
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+                &[DefaultedHalfmoveClock, DefaultedFullmoveNumber]
+            ),
+            (
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - - -",
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+                &[DefaultedHalfmoveClock, DefaultedFullmoveNumber]
+            ),
+            (
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 3",
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 3 1",
+                &[DefaultedFullmoveNumber]
+            ),
+            (
+                " rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR\tw  KQkq - 0 1\n",
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+                &[NormalizedWhitespace]
+            ),
+            (
+                "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e3 0 1",
+                "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 1",
+                &[RemovedEnPassant(Square::E3)]
+            ),
+            (
+                "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+                "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1",
+                &[RemovedEnPassant(Square::E3)]
+            ),
+            (
+                "rnbqkb1r/ppp1pppp/5n2/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3",
+                "rnbqkb1r/ppp1pppp/5n2/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3",
+                &[]
+            ),
+            (
+                "rnbqkbn1/pppppppp/8/8/8/8/PPPPPPPP/1NBQKBNR w KQkq - 0 1",
+                "rnbqkbn1/pppppppp/8/8/8/8/PPPPPPPP/1NBQKBNR w Kq - 0 1",
+                &[RemovedCastleRight('Q'), RemovedCastleRight('k')]
+            ),
+            (
+                "rnbq1bnr/ppppkppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+                "rnbq1bnr/ppppkppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQ - 0 1",
+                &[RemovedCastleRight('k'), RemovedCastleRight('q')]
+            )
+        ];
+        for &(fen, expected, expected_fixes) in cases {
+            let mut fixes = Vec::new();
+            let board = Board::from_fen_with(fen, FenOptions::lenient(FenFormat::XFen), |fix| fixes.push(fix))
+                .unwrap();
+            assert!(board.validity_check());
+            assert_eq!(format!("{}", board), expected, "{}", fen);
+            assert_eq!(fixes, expected_fixes, "{}", fen);
+            if let Ok(strict) = Board::from_fen(fen, FenFormat::XFen) {
+                assert_eq!(strict == board, expected_fixes.is_empty(), "{}", fen);
+            }
+        }
+    }
+
+    #[test]
+    fn lenient_fen_errors() {
+        let options = FenOptions::lenient(FenFormat::XFen);
+        let cases = [
+            ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR  x KQkq - 0 1", FenField::SideToMove, 45, FenParseErrorReason::InvalidSideToMove),
+            ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq", FenField::EnPassant, 50, FenParseErrorReason::MissingField),
+            ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq i6", FenField::EnPassant, 51, FenParseErrorReason::InvalidSquare),
+            ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KKkq - 0 1", FenField::CastleRights, 47, FenParseErrorReason::DuplicateCastleRight)
+        ];
+        for &(fen, field, offset, reason) in &cases {
+            let error = Board::from_fen_with(fen, options, |_| {}).unwrap_err();
+            assert_eq!(error, FenParseError { field, offset, reason }, "{}", fen);
         }
     }
 
     #[test]
     fn invalid_ep_fen() {
         let fen = "4k3/8/5N2/8/2pP4/8/8/4K3 b - d3 0 1";
-        assert!(matches!(fen.parse::<Board>(), Err(FenParseError::InvalidEnPassant)));
+        let error = fen.parse::<Board>().unwrap_err();
+        assert_eq!(error.field, FenField::EnPassant);
+        assert_eq!(error.reason, FenParseErrorReason::EnPassantImpossibleCheck);
     }
 }
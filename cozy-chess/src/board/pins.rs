@@ -0,0 +1,190 @@
+use crate::*;
+
+impl Board {
+    /// Iterate over the sliders of `color` that would attack `square` if not for exactly one
+    /// piece in the way, along with the square of that piece.
+    fn xray_lines(&self, square: Square, color: Color) -> impl Iterator<Item = (Square, Square)> + '_ {
+        let sliders = self.colors(color) & (
+            (get_bishop_rays(square) & (
+                self.pieces(Piece::Bishop) |
+                self.pieces(Piece::Queen)
+            )) |
+            (get_rook_rays(square) & (
+                self.pieces(Piece::Rook) |
+                self.pieces(Piece::Queen)
+            ))
+        );
+        sliders.into_iter().filter_map(move |slider| {
+            let between = get_between_rays(slider, square) & self.occupied();
+            if between.len() == 1 {
+                between.next_square().map(|blocker| (slider, blocker))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Get the enemy sliders pinning pieces of `color` to its king.
+    /// See [`Board::pinned`].
+    /// # Examples
+    /// ```
+    /// # use cozy_chess::*;
+    /// let board: Board = "8/8/1q4k1/5p2/1n6/3B4/1KP3r1/8 w - - 0 1".parse().unwrap();
+    /// assert_eq!(board.pinners(Color::White), Square::G2.bitboard());
+    /// assert_eq!(board.pinners(Color::Black), Square::D3.bitboard());
+    /// ```
+    pub fn pinners(&self, color: Color) -> BitBoard {
+        let our_pieces = self.colors(color);
+        self.xray_lines(self.king(color), !color)
+            .filter(|&(_, blocker)| our_pieces.has(blocker))
+            .fold(BitBoard::EMPTY, |pinners, (slider, _)| pinners | slider.bitboard())
+    }
+
+    /// Get the squares a pinned piece can move to without exposing its king,
+    /// which are the other squares between its king and the pinner, and the pinner itself.
+    /// Returns [`None`] if `square` does not hold a pinned piece.
+    /// See [`Board::pinned`].
+    /// # Examples
+    /// ```
+    /// # use cozy_chess::*;
+    /// let board: Board = "8/8/1q4k1/5p2/1n6/3B4/1KP3r1/8 w - - 0 1".parse().unwrap();
+    /// assert_eq!(board.pin_ray(Square::C2), Some(bitboard! {
+    ///     . . . . . . . .
+    ///     . . . . . . . .
+    ///     . . . . . . . .
+    ///     . . . . . . . .
+    ///     . . . . . . . .
+    ///     . . . . . . . .
+    ///     . . . X X X X .
+    ///     . . . . . . . .
+    /// }));
+    /// assert_eq!(board.pin_ray(Square::F5), Some(Square::E4.bitboard() | Square::D3.bitboard()));
+    /// assert_eq!(board.pin_ray(Square::B4), None);
+    /// ```
+    pub fn pin_ray(&self, square: Square) -> Option<BitBoard> {
+        let color = self.color_on(square)?;
+        let king = self.king(color);
+        if square == king {
+            return None;
+        }
+        self.xray_lines(king, !color)
+            .find(|&(_, blocker)| blocker == square)
+            .map(|(pinner, _)| (get_between_rays(king, pinner) | pinner.bitboard()) ^ square.bitboard())
+    }
+
+    /// Get the pieces of `color` that would give discovered check by moving,
+    /// because they are the only piece between one of `color`'s sliders and the enemy king.
+    /// # Examples
+    /// ```
+    /// # use cozy_chess::*;
+    /// let board: Board = "8/8/1q4k1/5p2/1n6/3B4/1KP3r1/8 w - - 0 1".parse().unwrap();
+    /// assert_eq!(board.discovered_check_candidates(Color::Black), Square::B4.bitboard());
+    /// assert_eq!(board.discovered_check_candidates(Color::White), BitBoard::EMPTY);
+    /// ```
+    pub fn discovered_check_candidates(&self, color: Color) -> BitBoard {
+        let our_pieces = self.colors(color);
+        self.xray_lines(self.king(!color), color)
+            .filter(|&(_, blocker)| our_pieces.has(blocker))
+            .fold(BitBoard::EMPTY, |candidates, (_, blocker)| candidates | blocker.bitboard())
+    }
+
+    /// Get the sliders of `color` that attack `square` through exactly one piece of either color.
+    /// These are the sliders that would attack `square` if that piece moved away or was captured.
+    /// # Examples
+    /// ```
+    /// # use cozy_chess::*;
+    /// let board: Board = "8/8/1q4k1/5p2/1n6/3B4/1KP3r1/8 w - - 0 1".parse().unwrap();
+    /// assert_eq!(board.xray_attacks(Square::B2, Color::Black), Square::B6.bitboard() | Square::G2.bitboard());
+    /// assert_eq!(board.xray_attacks(Square::G6, Color::White), Square::D3.bitboard());
+    /// assert_eq!(board.xray_attacks(Square::A1, Color::Black), BitBoard::EMPTY);
+    /// ```
+    pub fn xray_attacks(&self, square: Square, color: Color) -> BitBoard {
+        self.xray_lines(square, color)
+            .fold(BitBoard::EMPTY, |attackers, (slider, _)| attackers | slider.bitboard())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Slide from `from` in a direction until leaving the board,
+    /// returning the pieces in the way in order.
+    fn walk(board: &Board, from: Square, dx: i8, dy: i8) -> impl Iterator<Item = Square> + '_ {
+        let mut square = Some(from);
+        core::iter::from_fn(move || {
+            square = square?.try_offset(dx, dy);
+            square
+        }).filter(move |&square| board.occupied().has(square))
+    }
+
+    const DIRECTIONS: [(i8, i8, bool); 8] = [
+        (1, 0, false), (-1, 0, false), (0, 1, false), (0, -1, false),
+        (1, 1, true), (1, -1, true), (-1, 1, true), (-1, -1, true)
+    ];
+
+    fn is_slider_for(board: &Board, square: Square, color: Color, diagonal: bool) -> bool {
+        let slider = if diagonal { Piece::Bishop } else { Piece::Rook };
+        board.color_on(square) == Some(color)
+            && matches!(board.piece_on(square), Some(piece) if piece == slider || piece == Piece::Queen)
+    }
+
+    #[test]
+    fn pins_match_brute_force() {
+        for fen in include_str!("test_data/bench_positions.fens").lines() {
+            let board: Board = fen.parse().unwrap();
+            for color in Color::ALL {
+                let king = board.king(color);
+                let mut pinned = BitBoard::EMPTY;
+                let mut pinners = BitBoard::EMPTY;
+                let mut candidates = BitBoard::EMPTY;
+                for (dx, dy, diagonal) in DIRECTIONS {
+                    let mut pieces = walk(&board, king, dx, dy);
+                    if let (Some(first), Some(second)) = (pieces.next(), pieces.next()) {
+                        if board.color_on(first) == Some(color) && is_slider_for(&board, second, !color, diagonal) {
+                            pinned |= first.bitboard();
+                            pinners |= second.bitboard();
+                        }
+                    }
+                    let mut pieces = walk(&board, board.king(!color), dx, dy);
+                    if let (Some(first), Some(second)) = (pieces.next(), pieces.next()) {
+                        if board.color_on(first) == Some(color) && is_slider_for(&board, second, color, diagonal) {
+                            candidates |= first.bitboard();
+                        }
+                    }
+                }
+                assert_eq!(board.pinned(color), pinned, "{}", fen);
+                assert_eq!(board.pinners(color), pinners, "{}", fen);
+                assert_eq!(board.discovered_check_candidates(color), candidates, "{}", fen);
+            }
+
+            for square in Square::ALL {
+                // A pinned piece can only move along the pin ray, which the legal moves confirm
+                if board.pinned(board.side_to_move()).has(square) {
+                    let ray = board.pin_ray(square).unwrap();
+                    board.generate_moves_for(square.bitboard(), |moves| {
+                        assert!(ray.is_superset(moves.to), "{} {}", fen, square);
+                        false
+                    });
+                } else if let Some(color) = board.color_on(square) {
+                    assert_eq!(board.pin_ray(square).is_some(), board.pinned(color).has(square), "{}", fen);
+                } else {
+                    assert_eq!(board.pin_ray(square), None, "{}", fen);
+                }
+
+                for color in Color::ALL {
+                    let mut xrays = BitBoard::EMPTY;
+                    for (dx, dy, diagonal) in DIRECTIONS {
+                        let mut pieces = walk(&board, square, dx, dy).skip(1);
+                        if let Some(second) = pieces.next() {
+                            if is_slider_for(&board, second, color, diagonal) {
+                                xrays |= second.bitboard();
+                            }
+                        }
+                    }
+                    assert_eq!(board.xray_attacks(square, color), xrays, "{} {}", fen, square);
+                }
+            }
+        }
+    }
+}
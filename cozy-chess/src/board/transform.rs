@@ -0,0 +1,130 @@
+use crate::*;
+
+impl Board {
+    fn transform(&self, square_fn: impl Fn(Square) -> Square, swap_colors: bool) -> BoardBuilder {
+        let color_fn = |color: Color| if swap_colors {
+            !color
+        } else {
+            color
+        };
+        let original = BoardBuilder::from_board(self);
+        let mut builder = BoardBuilder::empty();
+        for &square in &Square::ALL {
+            *builder.square_mut(square_fn(square)) = original.square(square)
+                .map(|(piece, color)| (piece, color_fn(color)));
+        }
+        for &color in &Color::ALL {
+            *builder.castle_rights_mut(color_fn(color)) = *original.castle_rights(color);
+        }
+        builder.side_to_move = color_fn(original.side_to_move);
+        builder.en_passant = original.en_passant.map(square_fn);
+        builder.halfmove_clock = original.halfmove_clock;
+        builder.fullmove_number = original.fullmove_number;
+        builder
+    }
+
+    /// Flip the board vertically and swap the colors of all pieces.
+    /// Castle rights, the en passant square, and the side to move are swapped accordingly,
+    /// so the resulting position is equivalent to the original with the roles of the sides reversed.
+    /// # Examples
+    /// ```
+    /// # use cozy_chess::*;
+    /// let board: Board = "rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2"
+    ///     .parse().unwrap();
+    /// let expected: Board = "rnbqkb1r/pppp1ppp/5n2/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 1 2"
+    ///     .parse().unwrap();
+    /// assert_eq!(board.flip_colors(), expected);
+    /// assert_eq!(board.flip_colors().flip_colors(), board);
+    /// ```
+    pub fn flip_colors(&self) -> Board {
+        self.transform(Square::flip_rank, true)
+            .build()
+            .expect("Flipped board should be valid")
+    }
+
+    /// Mirror the board horizontally.
+    /// The en passant square is mirrored accordingly.
+    ///
+    /// Castling always places the king and rook on the same files regardless of orientation,
+    /// so a mirrored position with castle rights is not equivalent to the original.
+    /// This returns [`None`] if either side has castle rights.
+    /// # Examples
+    /// ```
+    /// # use cozy_chess::*;
+    /// let board: Board = "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1".parse().unwrap();
+    /// let expected: Board = "8/5p2/4p3/r5PK/k1p3R1/8/1P1P4/8 w - - 0 1".parse().unwrap();
+    /// assert_eq!(board.mirror(), Some(expected));
+    /// assert_eq!(Board::default().mirror(), None);
+    /// ```
+    pub fn mirror(&self) -> Option<Board> {
+        for &color in &Color::ALL {
+            if *self.castle_rights(color) != CastleRights::EMPTY {
+                return None;
+            }
+        }
+        let board = self.transform(Square::flip_file, false)
+            .build()
+            .expect("Mirrored board should be valid");
+        Some(board)
+    }
+
+    /// Get a canonical representative of the board under color flipping and mirroring.
+    /// The canonical board always has white to move. If neither side has castle rights,
+    /// it is also mirrored so that the white king is on files A to D.
+    /// Positions that are equivalent under these symmetries have the same canonical board,
+    /// up to the halfmove clock and fullmove number.
+    /// # Examples
+    /// ```
+    /// # use cozy_chess::*;
+    /// let board: Board = "8/8/8/8/3k4/8/6PK/8 b - - 0 1".parse().unwrap();
+    /// let canonical = board.canonicalize();
+    /// assert_eq!(canonical.side_to_move(), Color::White);
+    /// assert!(canonical.king(Color::White).file() <= File::D);
+    /// assert_eq!(canonical, canonical.flip_colors().canonicalize());
+    /// ```
+    pub fn canonicalize(&self) -> Board {
+        let board = if self.side_to_move() == Color::Black {
+            self.flip_colors()
+        } else {
+            self.clone()
+        };
+        if board.king(Color::White).file() > File::D {
+            if let Some(mirrored) = board.mirror() {
+                return mirrored;
+            }
+        }
+        board
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::perft;
+
+    #[test]
+    fn flip_colors_preserves_perft() {
+        for fen in include_str!("test_data/valid.sfens").lines().take(50) {
+            let board = Board::from_fen(fen, FenFormat::Shredder).unwrap();
+            let flipped = board.flip_colors();
+            assert!(flipped.validity_check());
+            assert_eq!(flipped.flip_colors(), board);
+            assert_eq!(perft(&board, 3), perft(&flipped, 3), "{}", fen);
+        }
+    }
+
+    #[test]
+    fn mirror_preserves_perft() {
+        let mut mirrored_any = false;
+        for fen in include_str!("test_data/valid.sfens").lines().take(200) {
+            let board = Board::from_fen(fen, FenFormat::Shredder).unwrap();
+            if let Some(mirrored) = board.mirror() {
+                mirrored_any = true;
+                assert!(mirrored.validity_check());
+                assert_eq!(mirrored.mirror(), Some(board.clone()));
+                assert_eq!(perft(&board, 3), perft(&mirrored, 3), "{}", fen);
+            }
+        }
+        assert!(mirrored_any);
+    }
+}
@@ -8,10 +8,37 @@ macro_rules! soft_assert {
     };
 }
 
+macro_rules! no_violations {
+    ($self:ident.$validate:ident()) => {{
+        let mut valid = true;
+        $self.$validate(&mut |_| valid = false);
+        valid
+    }};
+}
+
 impl Board {
     /// Canonical implementation of board validity. Used for debugging.
     #[cfg(test)]
     pub(crate) fn validity_check(&self) -> bool {
+        // The bitboards should not overlap.
+        let mut occupied = BitBoard::EMPTY;
+        for &piece in &Piece::ALL {
+            let pieces = self.pieces(piece);
+            soft_assert!((pieces & occupied).is_empty());
+            occupied |= pieces;
+        }
+        soft_assert!((self.colors(Color::White) & self.colors(Color::Black)).is_empty());
+        soft_assert!(occupied == self.occupied());
+
+        // The mailbox should agree with the bitboards.
+        for &square in &Square::ALL {
+            let piece = Piece::ALL.iter().copied().find(|&p| self.pieces(p).has(square));
+            soft_assert!(self.piece_on(square) == piece);
+        }
+
+        // The incrementally updated keys should match keys computed from scratch.
+        soft_assert!(self.inner.keys_are_valid());
+
         soft_assert!(self.board_is_valid());
         soft_assert!(self.checkers_and_pins_are_valid());
         soft_assert!(self.castle_rights_are_valid());
@@ -24,54 +51,123 @@ impl Board {
     /// Check if the just board is valid without considering "external" data like
     /// castle rights, en passant, or checker and pin info
     pub(super) fn board_is_valid(&self) -> bool {
-        // Verify that the board's data makes sense. The bitboards should not overlap.
-        let mut occupied = BitBoard::EMPTY;
-        for &piece in &Piece::ALL {
-            let pieces = self.pieces(piece);
-            soft_assert!((pieces & occupied).is_empty());
-            occupied |= pieces;
-        }
-        soft_assert!((self.colors(Color::White) & self.colors(Color::Black)).is_empty());
-        soft_assert!(occupied == self.occupied());
+        no_violations!(self.validate_pieces()) && no_violations!(self.validate_opponent_check())
+    }
+
+    pub(super) fn castle_rights_are_valid(&self) -> bool {
+        no_violations!(self.validate_castle_rights())
+    }
+
+    pub(super) fn en_passant_is_valid(&self) -> bool {
+        no_violations!(self.validate_en_passant())
+    }
+
+    pub(super) fn checkers_and_pins_are_valid(&self) -> bool {
+        let (checkers, pinned) = self.calculate_checkers_and_pins(self.side_to_move());
+        soft_assert!(self.checkers() == checkers);
+        soft_assert!(self.pinned == pinned);
+        no_violations!(self.validate_checkers())
+    }
+
+    pub(super) fn halfmove_clock_is_valid(&self) -> bool {
+        self.halfmove_clock <= 100
+    }
+
+    pub(super) fn fullmove_number_is_valid(&self) -> bool {
+        self.fullmove_number > 0
+    }
+
+    pub(super) fn kings_are_valid(&self) -> bool {
+        Color::ALL.iter().all(|&color| self.colored_pieces(color, Piece::King).len() == 1)
+    }
 
+    /// Report piece count and placement violations.
+    /// This is shared by [`BoardBuilder`] and the FEN parser, which adds the offset of the offending piece.
+    pub(super) fn validate_pieces(&self, listener: &mut dyn FnMut(BoardBuilderError)) {
+        let no_pawn_mask = Rank::First.bitboard() | Rank::Eighth.bitboard();
         for &color in &Color::ALL {
             let pieces = self.colors(color);
-            let no_pawn_mask = Rank::First.bitboard() | Rank::Eighth.bitboard();
-            soft_assert!(pieces.len() <= 16);
-            soft_assert!((pieces & self.pieces(Piece::King)).len() == 1);
-            soft_assert!((pieces & self.pieces(Piece::Pawn)).len() <= 8);
-            soft_assert!((pieces & self.pieces(Piece::Pawn) & no_pawn_mask).is_empty());
+            let kings = self.colored_pieces(color, Piece::King).len();
+            let pawns = self.colored_pieces(color, Piece::Pawn);
+            if kings == 0 {
+                listener(BoardBuilderError::MissingKing(color));
+            }
+            if kings > 1 {
+                listener(BoardBuilderError::TooManyKings(color));
+            }
+            if pawns.len() > 8 {
+                listener(BoardBuilderError::TooManyPawns(color));
+            }
+            if pieces.len() > 16 {
+                listener(BoardBuilderError::TooManyPieces(color));
+            }
+            for square in pawns & no_pawn_mask {
+                listener(BoardBuilderError::PawnOnBackRank(square));
+            }
+        }
+    }
+
+    /// Report if the side that just moved is in check. Requires valid kings.
+    pub(super) fn validate_opponent_check(&self, listener: &mut dyn FnMut(BoardBuilderError)) {
+        if !self.kings_are_valid() {
+            return;
         }
-        
         let (our_checkers, _) = self.calculate_checkers_and_pins(!self.side_to_move());
         // Opponent can't be in check while it's our turn
-        soft_assert!(our_checkers.is_empty());
+        if !our_checkers.is_empty() {
+            listener(BoardBuilderError::OpponentInCheck);
+        }
+    }
 
-        true
+    /// Report checkers that could not have been produced by a single move.
+    pub(super) fn validate_checkers(&self, listener: &mut dyn FnMut(BoardBuilderError)) {
+        let checkers = self.checkers();
+        if checkers.len() > 2 {
+            listener(BoardBuilderError::TooManyCheckers);
+        } else if checkers.len() == 2 {
+            // At most one checker can be the piece that moved. The other must have been
+            // discovered, so it is a slider, and the two can't share a line through the king.
+            let sliders = self.pieces(Piece::Bishop) | self.pieces(Piece::Rook) | self.pieces(Piece::Queen);
+            let mut iter = checkers.into_iter();
+            let (first, second) = (iter.next().unwrap(), iter.next().unwrap());
+            let our_king = self.king(self.side_to_move());
+            if (checkers & sliders).is_empty() || get_line_rays(first, second).has(our_king) {
+                listener(BoardBuilderError::ImpossibleCheckers);
+            }
+        }
     }
 
-    pub(super) fn castle_rights_are_valid(&self) -> bool {
+    pub(super) fn validate_castle_rights(&self, listener: &mut dyn FnMut(BoardBuilderError)) {
         for &color in &Color::ALL {
             let back_rank = Rank::First.relative_to(color);
             let rights = self.castle_rights(color);
             let our_rooks = self.colors(color) & self.pieces(Piece::Rook);
+            let our_king = match self.colored_pieces(color, Piece::King).next_square() {
+                Some(king) => king,
+                None => continue
+            };
             if rights.short.is_some() || rights.long.is_some() {
-                let our_king = self.king(color);
-                soft_assert!(our_king.rank() == back_rank);
+                if our_king.rank() != back_rank {
+                    listener(BoardBuilderError::KingNotOnBackRank(color));
+                    continue;
+                }
                 if let Some(rook) = rights.long {
-                    soft_assert!(our_rooks.has(Square::new(rook, back_rank)));
-                    soft_assert!(rook < our_king.file());
+                    if !our_rooks.has(Square::new(rook, back_rank)) || rook >= our_king.file() {
+                        listener(BoardBuilderError::CastleRightWithoutRook(color, rook));
+                    }
                 }
                 if let Some(rook) = rights.short {
-                    soft_assert!(our_rooks.has(Square::new(rook, back_rank)));
-                    soft_assert!(our_king.file() < rook);
+                    if !our_rooks.has(Square::new(rook, back_rank)) || our_king.file() >= rook {
+                        listener(BoardBuilderError::CastleRightWithoutRook(color, rook));
+                    }
                 }
             }
         }
-        true
     }
 
-    pub(super) fn en_passant_is_valid(&self) -> bool {
+    /// Report if the en passant square could not have been left by the last move.
+    /// This is shared by [`BoardBuilder`] and the FEN parser, which checks the rank of the square itself.
+    pub(super) fn validate_en_passant(&self, listener: &mut dyn FnMut(BoardBuilderError)) {
         let color = self.side_to_move();
         if let Some(ep) = self.en_passant() {
             let ep_source = Square::new(
@@ -87,37 +183,26 @@ impl Board {
                 Rank::Fourth.relative_to(!color)
             );
             let enemy_pawns = self.colored_pieces(!color, Piece::Pawn);
-            let our_king = self.king(color);
-            
-            soft_assert!(!self.occupied().has(ep_source));
-            soft_assert!(!self.occupied().has(ep_square));
-            soft_assert!(enemy_pawns.has(ep_pawn));
+
+            if !enemy_pawns.has(ep_pawn) {
+                listener(BoardBuilderError::EnPassantWithoutPawn);
+            }
+            if self.occupied().has(ep_source) || self.occupied().has(ep_square) {
+                listener(BoardBuilderError::EnPassantSquareOccupied);
+            }
             for checker in self.checkers() {
                 // The checker must either be:
                 // - The ep pawn itself
                 // - A slider that has rays going through the ep source.
                 // Knights and pawns are handled because they have no in-between rays for their movement.
+                let our_king = self.king(color);
                 let goes_through_ep_source = get_between_rays(checker, our_king).has(ep_source);
-                soft_assert!(checker == ep_pawn || goes_through_ep_source);
+                if checker != ep_pawn && !goes_through_ep_source {
+                    listener(BoardBuilderError::EnPassantImpossibleCheck);
+                    break;
+                }
             }
         }
-        true
-    }
-
-    pub(super) fn checkers_and_pins_are_valid(&self) -> bool {
-        let (checkers, pinned) = self.calculate_checkers_and_pins(self.side_to_move());
-        soft_assert!(self.checkers() == checkers);
-        soft_assert!(self.pinned() == pinned);
-        soft_assert!(self.checkers().len() < 3);
-        true
-    }
-
-    pub(super) fn halfmove_clock_is_valid(&self) -> bool {
-        self.halfmove_clock <= 100
-    }
-
-    pub(super) fn fullmove_number_is_valid(&self) -> bool {
-        self.fullmove_number > 0
     }
 
     pub(super) fn calculate_checkers_and_pins(&self, color: Color) -> (BitBoard, BitBoard) {
@@ -10,10 +10,13 @@ struct ColorZobristConstants {
 struct ZobristConstants {
     color: [ColorZobristConstants; Color::NUM],
     en_passant: [u64; File::NUM],
-    black_to_move: u64
+    black_to_move: u64,
+    // Summed once per piece, so the material key only depends on piece counts.
+    material: [[u64; Piece::NUM]; Color::NUM]
 }
 
-const ZOBRIST: ZobristConstants = {
+// A reference, since indexing a large constant can copy it to the stack at every use.
+const ZOBRIST: &ZobristConstants = &{
     // Simple Pcg64Mcg impl
     let mut state = 0x7369787465656E2062797465206E756Du128 | 1;
     macro_rules! rand {
@@ -62,22 +65,37 @@ const ZOBRIST: ZobristConstants = {
 
     let black_to_move = rand!();
 
+    // Generated last so the other keys don't change.
+    let mut material = [[0; Piece::NUM]; Color::NUM];
+    fill_array!(material: {
+        let mut pieces = [0; Piece::NUM];
+        fill_array!(pieces: rand!());
+        pieces
+    });
+
     ZobristConstants {
         color: [white, black],
         en_passant,
-        black_to_move
+        black_to_move,
+        material
     }
 };
 
 // This is Copy for performance reasons, since Copy guarantees a bit-for-bit copy.
+// The mailbox and the secondary keys are derived from the rest of the board,
+// so they don't change equality or hashing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct ZobristBoard {
     pieces: [BitBoard; Piece::NUM],
     colors: [BitBoard; Color::NUM],
+    mailbox: [Option<Piece>; Square::NUM],
     side_to_move: Color,
     castle_rights: [CastleRights; Color::NUM],
     en_passant: Option<File>,
-    hash: u64
+    hash: u64,
+    pawn_hash: u64,
+    non_pawn_hash: [u64; Color::NUM],
+    material_key: u64
 }
 
 impl ZobristBoard {
@@ -86,13 +104,17 @@ impl ZobristBoard {
         Self {
             pieces: [BitBoard::EMPTY; Piece::NUM],
             colors: [BitBoard::EMPTY; Color::NUM],
+            mailbox: [None; Square::NUM],
             side_to_move: Color::White,
             castle_rights: [CastleRights {
                 short: None,
                 long: None
             }; 2],
             en_passant: None,
-            hash: 0
+            hash: 0,
+            pawn_hash: 0,
+            non_pawn_hash: [0; Color::NUM],
+            material_key: 0
         }
     }
 
@@ -106,6 +128,11 @@ impl ZobristBoard {
         self.colors[color as usize]
     }
 
+    #[inline(always)]
+    pub fn piece_on(&self, square: Square) -> Option<Piece> {
+        self.mailbox[square as usize]
+    }
+
     #[inline(always)]
     pub fn side_to_move(&self) -> Color {
         self.side_to_move
@@ -135,6 +162,21 @@ impl ZobristBoard {
         hash
     }
 
+    #[inline(always)]
+    pub fn pawn_hash(&self) -> u64 {
+        self.pawn_hash
+    }
+
+    #[inline(always)]
+    pub fn non_pawn_hash(&self, color: Color) -> u64 {
+        self.non_pawn_hash[color as usize]
+    }
+
+    #[inline(always)]
+    pub fn material_key(&self) -> u64 {
+        self.material_key
+    }
+
     pub fn board_is_equal(&self, other: &Self) -> bool {
         self.pieces == other.pieces
             && self.colors == other.colors
@@ -147,10 +189,27 @@ impl ZobristBoard {
         let square_bb = square.bitboard();
         self.pieces[piece as usize] ^= square_bb;
         self.colors[color as usize] ^= square_bb;
-        self.hash ^= ZOBRIST
+        let slot = &mut self.mailbox[square as usize];
+        let added = slot.is_none();
+        *slot = if added { Some(piece) } else { None };
+
+        let piece_hash = ZOBRIST
             .color[color as usize]
             .pieces[piece as usize]
             [square as usize];
+        self.hash ^= piece_hash;
+        if piece == Piece::Pawn {
+            self.pawn_hash ^= piece_hash;
+        } else {
+            self.non_pawn_hash[color as usize] ^= piece_hash;
+        }
+
+        let material = ZOBRIST.material[color as usize][piece as usize];
+        self.material_key = if added {
+            self.material_key.wrapping_add(material)
+        } else {
+            self.material_key.wrapping_sub(material)
+        };
     }
 
     pub fn set_castle_right(&mut self, color: Color, short: bool, file: Option<File>)  {
@@ -182,11 +241,52 @@ impl ZobristBoard {
         self.side_to_move = !self.side_to_move;
         self.hash ^= ZOBRIST.black_to_move;
     }
+
+    /// Check the incrementally updated keys against keys computed from scratch.
+    #[cfg(test)]
+    pub fn keys_are_valid(&self) -> bool {
+        let mut hash = 0;
+        let mut pawn_hash = 0;
+        let mut non_pawn_hash = [0; Color::NUM];
+        let mut material_key: u64 = 0;
+        for &color in &Color::ALL {
+            let constants = &ZOBRIST.color[color as usize];
+            for &piece in &Piece::ALL {
+                let pieces = self.pieces(piece) & self.colors(color);
+                for square in pieces {
+                    let piece_hash = constants.pieces[piece as usize][square as usize];
+                    hash ^= piece_hash;
+                    if piece == Piece::Pawn {
+                        pawn_hash ^= piece_hash;
+                    } else {
+                        non_pawn_hash[color as usize] ^= piece_hash;
+                    }
+                }
+                let material = ZOBRIST.material[color as usize][piece as usize];
+                material_key = material_key.wrapping_add(material.wrapping_mul(pieces.len() as u64));
+            }
+            let rights = self.castle_rights(color);
+            for file in rights.short.into_iter().chain(rights.long) {
+                hash ^= constants.castle_rights[file as usize];
+            }
+        }
+        if let Some(file) = self.en_passant {
+            hash ^= ZOBRIST.en_passant[file as usize];
+        }
+        if self.side_to_move == Color::Black {
+            hash ^= ZOBRIST.black_to_move;
+        }
+        self.hash == hash
+            && self.pawn_hash == pawn_hash
+            && self.non_pawn_hash == non_pawn_hash
+            && self.material_key == material_key
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::Board;
+    use crate::{Board, Color};
+    use crate::test_util::*;
 
     #[test]
     fn zobrist_transpositions() {
@@ -224,6 +324,18 @@ mod tests {
                 board_b.play_unchecked(mv.parse().unwrap());
             }
             assert_eq!(board_a.hash(), board_b.hash(), "Test {}", i + 1);
+            assert_eq!(board_a.pawn_hash(), board_b.pawn_hash(), "Test {}", i + 1);
+            assert_eq!(board_a.material_key(), board_b.material_key(), "Test {}", i + 1);
+            for color in Color::ALL {
+                assert_eq!(board_a.non_pawn_hash(color), board_b.non_pawn_hash(color), "Test {}", i + 1);
+            }
+        }
+    }
+
+    #[test]
+    fn incremental_state_matches_board() {
+        for board in test_positions() {
+            for_each_position(&board, 2, &mut |board| assert!(board.validity_check(), "{}", board));
         }
     }
 }
@@ -0,0 +1,96 @@
+//! Support for fuzzing with [`arbitrary`](::arbitrary).
+//!
+//! With the `arbitrary` feature enabled, [`Board`] and the basic chess types implement
+//! [`Arbitrary`]. Arbitrary boards are always valid. They are generated either by a random
+//! playout from a standard or Chess960 start position, or by a random placement of a random
+//! amount of material. Smaller inputs produce simpler boards; an empty input produces the
+//! standard start position, which lets fuzzers minimize failing cases toward simple positions.
+
+use ::arbitrary::{Arbitrary, Error, Result, Unstructured};
+
+use crate::*;
+use crate::random::*;
+
+/// The maximum number of plies played from the start position for an arbitrary [`Board`].
+pub const MAX_PLIES: usize = 200;
+
+impl<'a> Arbitrary<'a> for Board {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        // Every choice falls back to its simplest option when the input runs out.
+        if u.arbitrary()? {
+            let side_to_move = u.arbitrary()?;
+            let mut material = [[0; Piece::NUM]; Color::NUM];
+            for counts in &mut material {
+                counts[Piece::Pawn as usize] = u.int_in_range(0..=8)?;
+                counts[Piece::Knight as usize] = u.int_in_range(0..=2)?;
+                counts[Piece::Bishop as usize] = u.int_in_range(0..=2)?;
+                counts[Piece::Rook as usize] = u.int_in_range(0..=2)?;
+                counts[Piece::Queen as usize] = u.int_in_range(0..=1)?;
+                counts[Piece::King as usize] = 1;
+            }
+            let mut rng = Rng::new(u.arbitrary()?);
+            random_position(&material, side_to_move, &mut rng).ok_or(Error::IncorrectFormat)
+        } else {
+            let plies = u.int_in_range(0..=MAX_PLIES)?;
+            let mut board = if u.arbitrary()? {
+                Board::chess960_startpos(u.int_in_range(0..=959)?)
+            } else {
+                Board::default()
+            };
+            let mut rng = Rng::new(u.arbitrary()?);
+            random_playout(&mut board, plies, &mut rng);
+            Ok(board)
+        }
+    }
+}
+
+/// Pick an arbitrary legal move for a board, or [`None`] if there are no legal moves.
+/// # Examples
+/// ```
+/// # use cozy_chess::*;
+/// use arbitrary::Unstructured;
+///
+/// let board = Board::default();
+/// let mut u = Unstructured::new(&[3, 1, 4, 1, 5]);
+/// let mv = fuzz::legal_move(&mut u, &board).unwrap().unwrap();
+/// assert!(board.is_legal(mv));
+/// ```
+pub fn legal_move(u: &mut Unstructured, board: &impl Position) -> Result<Option<Move>> {
+    let mut moves = Vec::new();
+    board.generate_moves(|mvs| {
+        moves.extend(mvs);
+        false
+    });
+    if moves.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(*u.choose(&moves)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_input_is_startpos() {
+        let mut u = Unstructured::new(&[]);
+        assert_eq!(Board::arbitrary(&mut u).unwrap(), Board::default());
+    }
+
+    #[test]
+    fn arbitrary_boards_are_valid() {
+        let mut rng = Rng::new(8);
+        for _ in 0..200 {
+            let data: Vec<u8> = (0..64).map(|_| rng.next_u64() as u8).collect();
+            let mut u = Unstructured::new(&data);
+            if let Ok(board) = Board::arbitrary(&mut u) {
+                assert!(board.validity_check());
+                if let Some(mv) = legal_move(&mut u, &board).unwrap() {
+                    assert!(board.is_legal(mv));
+                } else {
+                    assert_ne!(board.status(), GameStatus::Ongoing);
+                }
+            }
+        }
+    }
+}
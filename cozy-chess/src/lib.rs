@@ -15,7 +15,19 @@ pub use chess_move::*;
 
 mod board;
 mod moves;
+mod position;
 pub mod util;
+pub mod nnue;
+pub mod random;
+#[cfg(feature = "std")]
+pub mod svg;
+#[cfg(feature = "arbitrary")]
+pub mod fuzz;
+#[cfg(feature = "proptest")]
+pub mod strategy;
+#[cfg(test)]
+mod test_util;
 
 pub use board::*;
 pub use moves::*;
+pub use position::*;
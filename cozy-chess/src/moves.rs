@@ -31,9 +31,30 @@ include!(concat!(env!("OUT_DIR"), "/sliding_moves.rs"));
 /// ```
 #[inline(always)]
 pub fn get_rook_moves(square: Square, blockers: BitBoard) -> BitBoard {
+    #[cfg(feature = "hyperbola")]
+    return get_rook_moves_hyperbola(square, blockers);
+    #[cfg(not(feature = "hyperbola"))]
     BitBoard(SLIDING_MOVES[get_rook_moves_index(square, blockers)])
 }
 
+/// Check if slider lookups use PEXT bitboards.
+/// This is always `false` with the `hyperbola` feature, which takes precedence over the others,
+/// and always `true` with the `pext` feature. Otherwise, on x86_64 this is detected at runtime,
+/// and is `true` if the CPU supports BMI2 and implements PEXT in hardware.
+/// AMD CPUs before Zen 3 implement PEXT in microcode, so they use magic bitboards instead,
+/// as do other architectures.
+/// # Examples
+/// ```
+/// # use cozy_chess::*;
+/// if uses_pext() {
+///     println!("Using PEXT bitboards");
+/// }
+/// ```
+#[inline(always)]
+pub fn uses_pext() -> bool {
+    !cfg!(feature = "hyperbola") && uses_pext_index()
+}
+
 /// Significantly slower `const` version of [`get_rook_moves`].
 pub const fn get_rook_moves_const(square: Square, blockers: BitBoard) -> BitBoard {
     get_rook_moves_slow(square, blockers)
@@ -68,6 +89,9 @@ pub const fn get_rook_moves_const(square: Square, blockers: BitBoard) -> BitBoar
 /// ```
 #[inline(always)]
 pub fn get_bishop_moves(square: Square, blockers: BitBoard) -> BitBoard {
+    #[cfg(feature = "hyperbola")]
+    return get_bishop_moves_hyperbola(square, blockers);
+    #[cfg(not(feature = "hyperbola"))]
     BitBoard(SLIDING_MOVES[get_bishop_moves_index(square, blockers)])
 }
 
@@ -174,6 +198,7 @@ pub const fn get_between_rays(from: Square, to: Square) -> BitBoard {
         }
         between
     }
+    #[allow(clippy::large_const_arrays)]
     const TABLE: [[BitBoard; Square::NUM]; Square::NUM] = {
         let mut table = [[BitBoard::EMPTY; Square::NUM]; Square::NUM];
         let mut i = 0;
@@ -222,6 +247,7 @@ pub const fn get_line_rays(from: Square, to: Square) -> BitBoard {
         }
         BitBoard::EMPTY
     }
+    #[allow(clippy::large_const_arrays)]
     const TABLE: [[BitBoard; Square::NUM]; Square::NUM] = {
         let mut table = [[BitBoard::EMPTY; Square::NUM]; Square::NUM];
         let mut i = 0;
@@ -455,3 +481,44 @@ pub const fn get_pawn_quiets(square: Square, color: Color, blockers: BitBoard) -
     }
     moves
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slider_lookups_match_slow() {
+        for square in Square::ALL {
+            for blockers in get_rook_relevant_blockers(square).iter_subsets() {
+                let expected = get_rook_moves_const(square, blockers);
+                assert_eq!(get_rook_moves(square, blockers), expected);
+                assert_eq!(get_rook_moves_hyperbola(square, blockers), expected);
+                assert_eq!(get_rook_moves_hyperbola(square, blockers | BitBoard::EDGES), get_rook_moves_const(square, blockers | BitBoard::EDGES));
+                #[cfg(not(feature = "hyperbola"))]
+                assert_eq!(BitBoard(SLIDING_MOVES[get_rook_moves_index(square, blockers)]), expected);
+                #[cfg(all(not(feature = "hyperbola"), not(feature = "pext")))]
+                assert_eq!(BitBoard(SLIDING_MOVES[magic::get_rook_moves_index(square, blockers)]), expected);
+                #[cfg(all(not(feature = "hyperbola"), not(feature = "pext"), target_arch = "x86_64"))]
+                assert_eq!(BitBoard(SLIDING_MOVES[PEXT_TABLE_OFFSET + pext::get_rook_moves_index(square, blockers)]), expected);
+            }
+            for blockers in get_bishop_relevant_blockers(square).iter_subsets() {
+                let expected = get_bishop_moves_const(square, blockers);
+                assert_eq!(get_bishop_moves(square, blockers), expected);
+                assert_eq!(get_bishop_moves_hyperbola(square, blockers), expected);
+                assert_eq!(get_bishop_moves_hyperbola(square, blockers | BitBoard::EDGES), get_bishop_moves_const(square, blockers | BitBoard::EDGES));
+                #[cfg(not(feature = "hyperbola"))]
+                assert_eq!(BitBoard(SLIDING_MOVES[get_bishop_moves_index(square, blockers)]), expected);
+                #[cfg(all(not(feature = "hyperbola"), not(feature = "pext")))]
+                assert_eq!(BitBoard(SLIDING_MOVES[magic::get_bishop_moves_index(square, blockers)]), expected);
+                #[cfg(all(not(feature = "hyperbola"), not(feature = "pext"), target_arch = "x86_64"))]
+                assert_eq!(BitBoard(SLIDING_MOVES[PEXT_TABLE_OFFSET + pext::get_bishop_moves_index(square, blockers)]), expected);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "hyperbola")]
+    fn hyperbola_takes_precedence() {
+        assert!(!uses_pext());
+    }
+}
@@ -0,0 +1,355 @@
+//! Feature indexing helpers for [NNUE](https://www.chessprogramming.org/NNUE) evaluation.
+//!
+//! Networks index their inputs from the point of view of a "perspective" side.
+//! Squares are flipped vertically for black so that both sides see their own pieces
+//! moving up the board, and may additionally be mirrored horizontally (see [`Square::flip_file`])
+//! so that the perspective's king is always on files A to D.
+//!
+//! # Examples
+//! ```
+//! # use cozy_chess::*;
+//! # use cozy_chess::nnue::*;
+//! let features = HalfKp::new(KingBuckets::SQUARES);
+//! let mut board = Board::default();
+//! let mut accumulator = Vec::new();
+//! active_features(&features, &board, Color::White, |index| accumulator.push(index));
+//! assert_eq!(accumulator.len(), 30);
+//!
+//! let mv = "g1f3".parse().unwrap();
+//! let refreshed = !feature_updates(&features, &board, mv, Color::White, |update| match update {
+//!     FeatureUpdate::Add(index) => accumulator.push(index),
+//!     FeatureUpdate::Remove(index) => accumulator.retain(|&i| i != index)
+//! });
+//! assert!(!refreshed);
+//! board.play(mv);
+//!
+//! let mut expected = Vec::new();
+//! active_features(&features, &board, Color::White, |index| expected.push(index));
+//! accumulator.sort_unstable();
+//! expected.sort_unstable();
+//! assert_eq!(accumulator, expected);
+//! ```
+
+use crate::*;
+
+/// A mapping from king squares to input buckets.
+/// The bucket table is indexed by the king square as seen from the perspective side,
+/// after mirroring if mirroring is enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KingBuckets {
+    buckets: [u8; Square::NUM],
+    mirror: bool,
+    num_buckets: usize
+}
+
+impl KingBuckets {
+    /// One bucket per king square without mirroring. This is the layout used by HalfKP.
+    pub const SQUARES: Self = {
+        let mut buckets = [0; Square::NUM];
+        let mut i = 0;
+        while i < buckets.len() {
+            buckets[i] = i as u8;
+            i += 1;
+        }
+        Self::new(buckets, false)
+    };
+
+    /// One bucket per king square on files A to D with horizontal mirroring.
+    /// This is the 32 bucket layout used by HalfKAv2_hm.
+    pub const MIRRORED_SQUARES: Self = {
+        let mut buckets = [0; Square::NUM];
+        let mut i = 0;
+        while i < buckets.len() {
+            let square = Square::index_const(i);
+            let file = square.file() as usize;
+            if file < 4 {
+                buckets[i] = (square.rank() as usize * 4 + file) as u8;
+            }
+            i += 1;
+        }
+        Self::new(buckets, true)
+    };
+
+    /// Create king buckets from a bucket table.
+    /// If `mirror` is set, squares are mirrored horizontally whenever the perspective's king
+    /// is on files E to H, and only the entries for files A to D are used.
+    /// # Examples
+    /// ```
+    /// # use cozy_chess::*;
+    /// # use cozy_chess::nnue::*;
+    /// // One bucket for the back rank and another for everything else.
+    /// let mut table = [1; Square::NUM];
+    /// for square in Rank::First.bitboard() {
+    ///     table[square as usize] = 0;
+    /// }
+    /// let buckets = KingBuckets::new(table, true);
+    /// assert_eq!(buckets.num_buckets(), 2);
+    /// assert_eq!(buckets.bucket(Color::White, Square::G1), (0, true));
+    /// assert_eq!(buckets.bucket(Color::Black, Square::G1), (1, true));
+    /// ```
+    pub const fn new(buckets: [u8; Square::NUM], mirror: bool) -> Self {
+        let mut num_buckets = 0;
+        let mut i = 0;
+        while i < buckets.len() {
+            let file = Square::index_const(i).file() as usize;
+            if (!mirror || file < 4) && buckets[i] as usize + 1 > num_buckets {
+                num_buckets = buckets[i] as usize + 1;
+            }
+            i += 1;
+        }
+        Self {
+            buckets,
+            mirror,
+            num_buckets
+        }
+    }
+
+    /// Get the number of buckets.
+    pub const fn num_buckets(&self) -> usize {
+        self.num_buckets
+    }
+
+    /// Check if mirroring is enabled.
+    pub const fn mirror(&self) -> bool {
+        self.mirror
+    }
+
+    /// Get the bucket for a king square from some perspective,
+    /// and whether squares should be mirrored horizontally.
+    pub fn bucket(&self, perspective: Color, king: Square) -> (usize, bool) {
+        let king = king.relative_to(perspective);
+        let mirrored = self.mirror && king.file() > File::D;
+        let king = if mirrored {
+            king.flip_file()
+        } else {
+            king
+        };
+        (self.buckets[king as usize] as usize, mirrored)
+    }
+}
+
+/// A set of input features indexed relative to the perspective's king.
+pub trait FeatureSet {
+    /// The number of input features.
+    fn num_features(&self) -> usize;
+
+    /// The king buckets used by this feature set.
+    fn king_buckets(&self) -> &KingBuckets;
+
+    /// Get the feature index for a piece, given the perspective and its king square.
+    /// Returns [`None`] if the piece is not an input feature.
+    fn index(&self, perspective: Color, king: Square, piece: Piece, color: Color, square: Square) -> Option<usize>;
+}
+
+// Perspective relative square and piece plane shared by the feature sets.
+#[inline(always)]
+fn orient(buckets: &KingBuckets, perspective: Color, king: Square, color: Color, square: Square) -> (usize, usize, Square) {
+    let (bucket, mirrored) = buckets.bucket(perspective, king);
+    let mut square = square.relative_to(perspective);
+    if mirrored {
+        square = square.flip_file();
+    }
+    let relative_color = (color != perspective) as usize;
+    (bucket, relative_color, square)
+}
+
+/// The HalfKP feature set.
+/// Each non-king piece is a feature indexed by its type, its color relative
+/// to the perspective, its square, and the perspective's king bucket.
+/// The index is `(bucket * 10 + piece * 2 + relative_color) * 64 + square`.
+///
+/// This is not Stockfish's HalfKP layout. Stockfish has 41024 features instead of 40960,
+/// since it reserves an extra feature per king square and orders its piece planes differently,
+/// so its networks cannot be used with these indices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HalfKp {
+    buckets: KingBuckets
+}
+
+impl HalfKp {
+    /// Create a HalfKP feature set.
+    /// Use [`KingBuckets::SQUARES`] for one bucket per king square.
+    pub const fn new(buckets: KingBuckets) -> Self {
+        Self { buckets }
+    }
+}
+
+impl FeatureSet for HalfKp {
+    fn num_features(&self) -> usize {
+        self.buckets.num_buckets() * 10 * Square::NUM
+    }
+
+    fn king_buckets(&self) -> &KingBuckets {
+        &self.buckets
+    }
+
+    #[inline(always)]
+    fn index(&self, perspective: Color, king: Square, piece: Piece, color: Color, square: Square) -> Option<usize> {
+        if piece == Piece::King {
+            return None;
+        }
+        let (bucket, relative_color, square) = orient(&self.buckets, perspective, king, color, square);
+        let plane = piece as usize * 2 + relative_color;
+        Some((bucket * 10 + plane) * Square::NUM + square as usize)
+    }
+}
+
+/// The HalfKAv2 feature set.
+/// Every piece is a feature indexed by its type, its color relative to the perspective,
+/// its square, and the perspective's king bucket. Both kings share a single plane,
+/// since they can never occupy the same square.
+/// The index is `(bucket * 11 + plane) * 64 + square`, where `plane` is
+/// `piece * 2 + relative_color` for non-king pieces and `10` for kings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HalfKaV2 {
+    buckets: KingBuckets
+}
+
+impl HalfKaV2 {
+    /// Create a HalfKAv2 feature set.
+    /// HalfKAv2_hm uses [`KingBuckets::MIRRORED_SQUARES`].
+    pub const fn new(buckets: KingBuckets) -> Self {
+        Self { buckets }
+    }
+}
+
+impl FeatureSet for HalfKaV2 {
+    fn num_features(&self) -> usize {
+        self.buckets.num_buckets() * 11 * Square::NUM
+    }
+
+    fn king_buckets(&self) -> &KingBuckets {
+        &self.buckets
+    }
+
+    #[inline(always)]
+    fn index(&self, perspective: Color, king: Square, piece: Piece, color: Color, square: Square) -> Option<usize> {
+        let (bucket, relative_color, square) = orient(&self.buckets, perspective, king, color, square);
+        let plane = if piece == Piece::King {
+            10
+        } else {
+            piece as usize * 2 + relative_color
+        };
+        Some((bucket * 11 + plane) * Square::NUM + square as usize)
+    }
+}
+
+/// A change to the active features.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FeatureUpdate {
+    /// The feature with this index became active.
+    Add(usize),
+    /// The feature with this index became inactive.
+    Remove(usize)
+}
+
+/// Enumerate the active feature indices for a board from some perspective.
+/// Features are produced in no particular order.
+pub fn active_features<F: FeatureSet>(
+    features: &F, board: &Board, perspective: Color, mut listener: impl FnMut(usize)
+) {
+    let king = board.king(perspective);
+    for &color in &Color::ALL {
+        for &piece in &Piece::ALL {
+            for square in board.colored_pieces(color, piece) {
+                if let Some(index) = features.index(perspective, king, piece, color, square) {
+                    listener(index);
+                }
+            }
+        }
+    }
+}
+
+/// Compute the feature updates from some perspective caused by playing a move.
+/// The move is assumed to be legal.
+///
+/// Returns `false` without reporting any updates if the move changes the
+/// perspective's king bucket or mirroring, in which case the active features
+/// must be refreshed with [`active_features`] after the move is played.
+pub fn feature_updates<F: FeatureSet>(
+    features: &F, board: &Board, mv: Move, perspective: Color, mut listener: impl FnMut(FeatureUpdate)
+) -> bool {
+    // A move makes at most four placement changes (castling).
+    let mut deltas = [None; 4];
+    let mut len = 0;
+    let mut after = board.clone();
+    after.play_unchecked_with(mv, |delta| {
+        deltas[len] = Some(delta);
+        len += 1;
+    });
+
+    let buckets = features.king_buckets();
+    let king = after.king(perspective);
+    if buckets.bucket(perspective, board.king(perspective)) != buckets.bucket(perspective, king) {
+        return false;
+    }
+    for delta in deltas.iter().flatten() {
+        if let Some(index) = features.index(perspective, king, delta.piece, delta.color, delta.square) {
+            listener(if delta.added {
+                FeatureUpdate::Add(index)
+            } else {
+                FeatureUpdate::Remove(index)
+            });
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::test_util::*;
+
+    fn collect<F: FeatureSet>(features: &F, board: &Board, perspective: Color) -> HashSet<usize> {
+        let mut active = HashSet::new();
+        active_features(features, board, perspective, |index| {
+            assert!(index < features.num_features());
+            assert!(active.insert(index), "duplicate feature {}", index);
+        });
+        active
+    }
+
+    fn check_updates<F: FeatureSet>(features: &F) {
+        for board in test_positions() {
+            for_each_move(&board, 2, &mut |board, mv, child| {
+                for &perspective in &Color::ALL {
+                    let mut active = collect(features, board, perspective);
+                    let updated = feature_updates(features, board, mv, perspective, |update| match update {
+                        FeatureUpdate::Add(index) => assert!(active.insert(index)),
+                        FeatureUpdate::Remove(index) => assert!(active.remove(&index))
+                    });
+                    if updated {
+                        assert_eq!(active, collect(features, child, perspective), "{} {}", board, mv);
+                    }
+                }
+            });
+        }
+    }
+
+    #[test]
+    fn halfkp_updates_match_refresh() {
+        let features = HalfKp::new(KingBuckets::SQUARES);
+        assert_eq!(features.num_features(), 40960);
+        check_updates(&features);
+    }
+
+    #[test]
+    fn halfkav2_updates_match_refresh() {
+        let features = HalfKaV2::new(KingBuckets::MIRRORED_SQUARES);
+        assert_eq!(features.num_features(), 22528);
+        check_updates(&features);
+    }
+
+    #[test]
+    fn perspectives_are_symmetric() {
+        let features = HalfKaV2::new(KingBuckets::MIRRORED_SQUARES);
+        let white: Board = "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3"
+            .parse().unwrap();
+        let black: Board = "rnbqkb1r/pppp1ppp/5n2/4p3/4P3/2N5/PPPP1PPP/R1BQKBNR b KQkq - 2 3"
+            .parse().unwrap();
+        assert_eq!(collect(&features, &white, Color::White), collect(&features, &black, Color::Black));
+        assert_eq!(collect(&features, &white, Color::Black), collect(&features, &black, Color::White));
+    }
+}
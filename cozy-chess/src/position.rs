@@ -0,0 +1,325 @@
+use crate::*;
+
+/// The kind of a [`Move`], as classified by [`Position::move_kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MoveKind {
+    /// A move that does not capture, castle, or promote.
+    Quiet,
+    /// A move that captures an enemy piece on the destination square.
+    Capture,
+    /// An en passant capture.
+    EnPassant,
+    /// A castling move, encoded as the king capturing its own rook.
+    Castle,
+    /// A pawn promotion that does not capture.
+    Promotion,
+    /// A pawn promotion that captures an enemy piece.
+    CapturePromotion
+}
+
+/// A chess position that supports legal move generation.
+///
+/// This is the common interface shared by [`Board`] and any variant positions
+/// that reuse `cozy-chess`'s move representation. Utilities such as those in
+/// [`util`] are written against this trait so they work with any implementor.
+/// # Examples
+/// ```
+/// # use cozy_chess::*;
+/// fn perft(position: &impl Position, depth: u8) -> u64 {
+///     if depth == 0 {
+///         return 1;
+///     }
+///     let mut nodes = 0;
+///     position.generate_moves(|moves| {
+///         for mv in moves {
+///             let mut child = position.clone();
+///             child.play_unchecked(mv);
+///             nodes += perft(&child, depth - 1);
+///         }
+///         false
+///     });
+///     nodes
+/// }
+/// assert_eq!(perft(&Board::default(), 3), 8902);
+/// ```
+pub trait Position: Clone {
+    /// Get a [`BitBoard`] of all the pieces of a certain type.
+    /// See [`Board::pieces`].
+    fn pieces(&self, piece: Piece) -> BitBoard;
+
+    /// Get a [`BitBoard`] of all the pieces of a certain color.
+    /// See [`Board::colors`].
+    fn colors(&self, color: Color) -> BitBoard;
+
+    /// Get a [`BitBoard`] of all the pieces of a certain color and type.
+    /// See [`Board::colored_pieces`].
+    #[inline(always)]
+    fn colored_pieces(&self, color: Color, piece: Piece) -> BitBoard {
+        self.colors(color) & self.pieces(piece)
+    }
+
+    /// Get a [`BitBoard`] of all the pieces on the board.
+    /// See [`Board::occupied`].
+    #[inline(always)]
+    fn occupied(&self) -> BitBoard {
+        self.colors(Color::White) | self.colors(Color::Black)
+    }
+
+    /// Get the [`Piece`] on `square`, if there is one.
+    /// See [`Board::piece_on`].
+    fn piece_on(&self, square: Square) -> Option<Piece>;
+
+    /// Get the [`Color`] of the piece on `square`, if there is one.
+    /// See [`Board::color_on`].
+    fn color_on(&self, square: Square) -> Option<Color>;
+
+    /// Get the king square of some side.
+    /// See [`Board::king`].
+    fn king(&self, color: Color) -> Square;
+
+    /// Get the current side to move.
+    /// See [`Board::side_to_move`].
+    fn side_to_move(&self) -> Color;
+
+    /// Get the [`CastleRights`] for some side.
+    /// See [`Board::castle_rights`].
+    fn castle_rights(&self, color: Color) -> &CastleRights;
+
+    /// Get the en passant file, if it exists.
+    /// See [`Board::en_passant`].
+    fn en_passant(&self) -> Option<File>;
+
+    /// Get the fullmove number.
+    /// See [`Board::fullmove_number`].
+    fn fullmove_number(&self) -> u16;
+
+    /// Get the pieces currently giving check.
+    /// See [`Board::checkers`].
+    fn checkers(&self) -> BitBoard;
+
+    /// Get the position hash.
+    /// See [`Board::hash`].
+    /// This is not named `hash` to avoid clashing with [`core::hash::Hash::hash`].
+    /// # Examples
+    /// ```
+    /// # use cozy_chess::*;
+    /// fn key<P: Position + core::hash::Hash>(position: &P) -> u64 {
+    ///     position.zobrist_hash()
+    /// }
+    /// let board = Board::default();
+    /// assert_eq!(key(&board), board.hash());
+    /// ```
+    fn zobrist_hash(&self) -> u64;
+
+    /// Get the status of the game.
+    /// See [`Board::status`].
+    fn status(&self) -> GameStatus;
+
+    /// Generate legal moves for a subset of pieces.
+    /// See [`Board::generate_moves_for`].
+    fn generate_moves_for<F: FnMut(PieceMoves) -> bool>(&self, mask: BitBoard, listener: F) -> bool;
+
+    /// Generate all legal moves.
+    /// See [`Board::generate_moves`].
+    #[inline(always)]
+    fn generate_moves<F: FnMut(PieceMoves) -> bool>(&self, listener: F) -> bool {
+        self.generate_moves_for(BitBoard::FULL, listener)
+    }
+
+    /// See if a move is legal.
+    /// See [`Board::is_legal`].
+    fn is_legal(&self, mv: Move) -> bool;
+
+    /// Play a move without checking its legality.
+    /// See [`Board::play_unchecked`].
+    fn play_unchecked(&mut self, mv: Move);
+
+    /// Play a move while checking its legality.
+    /// See [`Board::try_play`].
+    /// # Errors
+    /// Errors with [`IllegalMoveError`] if the move was illegal.
+    fn try_play(&mut self, mv: Move) -> Result<(), IllegalMoveError> {
+        if !self.is_legal(mv) {
+            return Err(IllegalMoveError);
+        }
+        self.play_unchecked(mv);
+        Ok(())
+    }
+
+    /// Play a move while checking its legality.
+    /// See [`Board::play`].
+    /// # Panics
+    /// This is guaranteed to panic if the move is illegal.
+    fn play(&mut self, mv: Move) {
+        assert!(self.try_play(mv).is_ok(), "Illegal move {}!", mv);
+    }
+
+    /// Get the [`Piece`] moved by a move.
+    /// See [`Board::moved_piece`].
+    #[inline(always)]
+    fn moved_piece(&self, mv: Move) -> Option<Piece> {
+        self.piece_on(mv.from)
+    }
+
+    /// Check if a move is a castling move.
+    /// See [`Board::is_castle`].
+    #[inline(always)]
+    fn is_castle(&self, mv: Move) -> bool {
+        // Castling move encoded as king captures rook.
+        let color = self.side_to_move();
+        self.colored_pieces(color, Piece::King).has(mv.from)
+            && self.colors(color).has(mv.to)
+    }
+
+    /// Check if a move is an en passant capture.
+    /// See [`Board::is_en_passant`].
+    #[inline(always)]
+    fn is_en_passant(&self, mv: Move) -> bool {
+        let color = self.side_to_move();
+        match self.en_passant() {
+            Some(ep) => {
+                let ep_square = Square::new(ep, Rank::Sixth.relative_to(color));
+                mv.to == ep_square && self.colored_pieces(color, Piece::Pawn).has(mv.from)
+            }
+            None => false
+        }
+    }
+
+    /// Get the [`Piece`] captured by a move, if there is one.
+    /// See [`Board::captured_piece`].
+    #[inline(always)]
+    fn captured_piece(&self, mv: Move) -> Option<Piece> {
+        if self.is_en_passant(mv) {
+            Some(Piece::Pawn)
+        } else if self.colors(!self.side_to_move()).has(mv.to) {
+            self.piece_on(mv.to)
+        } else {
+            None
+        }
+    }
+
+    /// Check if a move captures a piece.
+    /// See [`Board::is_capture`].
+    #[inline(always)]
+    fn is_capture(&self, mv: Move) -> bool {
+        self.captured_piece(mv).is_some()
+    }
+
+    /// Classify a move.
+    /// See [`Board::move_kind`].
+    fn move_kind(&self, mv: Move) -> MoveKind {
+        if self.is_castle(mv) {
+            MoveKind::Castle
+        } else if self.is_en_passant(mv) {
+            MoveKind::EnPassant
+        } else {
+            match (self.is_capture(mv), mv.promotion.is_some()) {
+                (false, false) => MoveKind::Quiet,
+                (true, false) => MoveKind::Capture,
+                (false, true) => MoveKind::Promotion,
+                (true, true) => MoveKind::CapturePromotion
+            }
+        }
+    }
+}
+
+impl Position for Board {
+    #[inline(always)]
+    fn pieces(&self, piece: Piece) -> BitBoard {
+        Board::pieces(self, piece)
+    }
+
+    #[inline(always)]
+    fn colors(&self, color: Color) -> BitBoard {
+        Board::colors(self, color)
+    }
+
+    #[inline(always)]
+    fn colored_pieces(&self, color: Color, piece: Piece) -> BitBoard {
+        Board::colored_pieces(self, color, piece)
+    }
+
+    #[inline(always)]
+    fn occupied(&self) -> BitBoard {
+        Board::occupied(self)
+    }
+
+    #[inline(always)]
+    fn piece_on(&self, square: Square) -> Option<Piece> {
+        Board::piece_on(self, square)
+    }
+
+    #[inline(always)]
+    fn color_on(&self, square: Square) -> Option<Color> {
+        Board::color_on(self, square)
+    }
+
+    #[inline(always)]
+    fn king(&self, color: Color) -> Square {
+        Board::king(self, color)
+    }
+
+    #[inline(always)]
+    fn side_to_move(&self) -> Color {
+        Board::side_to_move(self)
+    }
+
+    #[inline(always)]
+    fn castle_rights(&self, color: Color) -> &CastleRights {
+        Board::castle_rights(self, color)
+    }
+
+    #[inline(always)]
+    fn en_passant(&self) -> Option<File> {
+        Board::en_passant(self)
+    }
+
+    #[inline(always)]
+    fn fullmove_number(&self) -> u16 {
+        Board::fullmove_number(self)
+    }
+
+    #[inline(always)]
+    fn checkers(&self) -> BitBoard {
+        Board::checkers(self)
+    }
+
+    #[inline(always)]
+    fn zobrist_hash(&self) -> u64 {
+        Board::hash(self)
+    }
+
+    fn status(&self) -> GameStatus {
+        Board::status(self)
+    }
+
+    #[inline(always)]
+    fn generate_moves_for<F: FnMut(PieceMoves) -> bool>(&self, mask: BitBoard, listener: F) -> bool {
+        Board::generate_moves_for(self, mask, listener)
+    }
+
+    fn is_legal(&self, mv: Move) -> bool {
+        Board::is_legal(self, mv)
+    }
+
+    fn play_unchecked(&mut self, mv: Move) {
+        Board::play_unchecked(self, mv)
+    }
+
+    fn try_play(&mut self, mv: Move) -> Result<(), IllegalMoveError> {
+        Board::try_play(self, mv)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::perft;
+
+    #[test]
+    fn generic_perft_matches_board() {
+        let board = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1"
+            .parse::<Board>().unwrap();
+        assert_eq!(perft(&board, 3), 97862);
+    }
+}
@@ -0,0 +1,244 @@
+//! Random move, game, and position generation.
+//!
+//! Everything here is driven by [`Rng`], a small seedable PRNG, so results are
+//! deterministic for a given seed and don't require `std`. Functions take the
+//! generator as their last argument.
+
+use crate::*;
+
+/// A small, fast, seedable pseudorandom number generator.
+/// This is a PCG64 MCG variant (the same algorithm used to generate `cozy-chess`'s zobrist keys).
+/// It is *not* cryptographically secure.
+/// # Examples
+/// ```
+/// # use cozy_chess::random::*;
+/// let mut a = Rng::new(0xC0FFEE);
+/// let mut b = Rng::new(0xC0FFEE);
+/// assert_eq!(a.next_u64(), b.next_u64());
+/// assert!(a.below(10) < 10);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Rng {
+    state: u128
+}
+
+impl Rng {
+    /// Create a generator from a seed.
+    pub fn new(seed: u64) -> Self {
+        let mut this = Self {
+            state: (((seed as u128) << 1) | 1) ^ 0x636F7A792D6368657373207365656420
+        };
+        // Mix the seed in before the first output.
+        this.next_u64();
+        this
+    }
+
+    /// Generate a random [`u64`].
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_mul(0x2360ED051FC65DA44385DF649FCCF645);
+        let rot = (self.state >> 122) as u32;
+        let xsl = (self.state >> 64) as u64 ^ self.state as u64;
+        xsl.rotate_right(rot)
+    }
+
+    /// Generate a uniformly random number in the range `0..n`.
+    /// # Panics
+    /// Panic if `n` is zero.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "Range must not be empty");
+        // Lemire's nearly divisionless method.
+        let threshold = n.wrapping_neg() % n;
+        loop {
+            let product = self.next_u64() as u128 * n as u128;
+            if product as u64 >= threshold {
+                return (product >> 64) as u64;
+            }
+        }
+    }
+}
+
+/// Pick a uniformly random legal move, or [`None`] if there are no legal moves.
+/// This only runs move generation once.
+/// # Examples
+/// ```
+/// # use cozy_chess::*;
+/// # use cozy_chess::random::*;
+/// let board = Board::default();
+/// let mut rng = Rng::new(1);
+/// let mv = random_move(&board, &mut rng).unwrap();
+/// assert!(board.is_legal(mv));
+/// ```
+pub fn random_move(position: &impl Position, rng: &mut Rng) -> Option<Move> {
+    // Weighted reservoir sampling over each group of moves.
+    let mut total = 0;
+    let mut chosen = None;
+    position.generate_moves(|moves| {
+        let len = moves.len() as u64;
+        total += len;
+        if rng.below(total) < len {
+            chosen = Some(moves);
+        }
+        false
+    });
+    let moves = chosen?;
+    moves.into_iter().nth(rng.below(moves.len() as u64) as usize)
+}
+
+/// Play random legal moves until `max_plies` moves have been played or the game is over.
+/// Returns the number of moves played.
+/// # Examples
+/// ```
+/// # use cozy_chess::*;
+/// # use cozy_chess::random::*;
+/// let mut board = Board::default();
+/// let mut rng = Rng::new(2);
+/// let plies = random_playout(&mut board, 8, &mut rng);
+/// assert_eq!(plies, 8);
+/// assert_eq!(board.fullmove_number(), 5);
+/// ```
+pub fn random_playout(position: &mut impl Position, max_plies: usize, rng: &mut Rng) -> usize {
+    for plies in 0..max_plies {
+        if position.status() != GameStatus::Ongoing {
+            return plies;
+        }
+        let mv = random_move(position, rng).expect("Ongoing game should have legal moves");
+        position.play_unchecked(mv);
+    }
+    max_plies
+}
+
+/// Generate a random legal position with the given material.
+/// `material` holds the number of pieces of each type, indexed by color and then by piece.
+/// Each side must have exactly one king. The side to move is `side_to_move`, there are no
+/// castle rights or en passant square, and the clocks are at their initial values.
+///
+/// Returns [`None`] if the material can't form a valid position, or if no valid placement
+/// was found after a number of attempts.
+/// # Examples
+/// ```
+/// # use cozy_chess::*;
+/// # use cozy_chess::random::*;
+/// let mut material = [[0; Piece::NUM]; Color::NUM];
+/// material[Color::White as usize][Piece::King as usize] = 1;
+/// material[Color::White as usize][Piece::Rook as usize] = 1;
+/// material[Color::Black as usize][Piece::King as usize] = 1;
+/// let mut rng = Rng::new(3);
+/// let board = random_position(&material, Color::White, &mut rng).unwrap();
+/// assert_eq!(board.pieces(Piece::Rook).len(), 1);
+/// assert_eq!(board.occupied().len(), 3);
+/// ```
+pub fn random_position(
+    material: &[[u8; Piece::NUM]; Color::NUM], side_to_move: Color, rng: &mut Rng
+) -> Option<Board> {
+    const ATTEMPTS: usize = 1000;
+
+    for counts in material {
+        let total: u32 = counts.iter().map(|&count| count as u32).sum();
+        if counts[Piece::King as usize] != 1 || counts[Piece::Pawn as usize] > 8 || total > 16 {
+            return None;
+        }
+    }
+
+    let pawn_squares = !(Rank::First.bitboard() | Rank::Eighth.bitboard());
+    'attempts: for _ in 0..ATTEMPTS {
+        let mut builder = BoardBuilder::empty();
+        builder.side_to_move = side_to_move;
+        let mut occupied = BitBoard::EMPTY;
+        for &color in &Color::ALL {
+            for &piece in &Piece::ALL {
+                for _ in 0..material[color as usize][piece as usize] {
+                    let mut free = !occupied;
+                    if piece == Piece::Pawn {
+                        free &= pawn_squares;
+                    }
+                    if free.is_empty() {
+                        continue 'attempts;
+                    }
+                    let index = rng.below(free.len() as u64) as usize;
+                    let square = free.iter().nth(index).unwrap();
+                    occupied |= square.bitboard();
+                    *builder.square_mut(square) = Some((piece, color));
+                }
+            }
+        }
+        if let Ok(board) = builder.build() {
+            return Some(board);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rng_is_deterministic() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        let mut c = Rng::new(43);
+        let a: [u64; 8] = core::array::from_fn(|_| a.next_u64());
+        let b: [u64; 8] = core::array::from_fn(|_| b.next_u64());
+        let c: [u64; 8] = core::array::from_fn(|_| c.next_u64());
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn random_move_is_uniform() {
+        // Promotions make the move groups uneven in size.
+        let board: Board = "4k3/1P6/8/8/8/8/8/4K3 w - - 0 1".parse().unwrap();
+        let mut moves = Vec::new();
+        board.generate_moves(|mvs| {
+            moves.extend(mvs);
+            false
+        });
+        let mut counts = vec![0; moves.len()];
+        let mut rng = Rng::new(5);
+        const SAMPLES: usize = 20000;
+        for _ in 0..SAMPLES {
+            let mv = random_move(&board, &mut rng).unwrap();
+            counts[moves.iter().position(|&m| m == mv).unwrap()] += 1;
+        }
+        let expected = SAMPLES / moves.len();
+        for count in counts {
+            assert!(count > expected * 8 / 10 && count < expected * 12 / 10, "{}", count);
+        }
+    }
+
+    #[test]
+    fn random_playouts_are_valid() {
+        let mut rng = Rng::new(6);
+        for _ in 0..100 {
+            let mut board = Board::default();
+            let plies = random_playout(&mut board, 200, &mut rng);
+            assert!(board.validity_check());
+            if plies < 200 {
+                assert_ne!(board.status(), GameStatus::Ongoing);
+            }
+        }
+    }
+
+    #[test]
+    fn random_positions_match_material() {
+        let mut rng = Rng::new(7);
+        let mut material = [[0; Piece::NUM]; Color::NUM];
+        material[0] = [3, 1, 1, 1, 1, 1];
+        material[1] = [8, 0, 2, 0, 0, 1];
+        for &side_to_move in &Color::ALL {
+            for _ in 0..100 {
+                let board = random_position(&material, side_to_move, &mut rng).unwrap();
+                assert!(board.validity_check());
+                assert_eq!(board.side_to_move(), side_to_move);
+                for &color in &Color::ALL {
+                    for &piece in &Piece::ALL {
+                        let count = board.colored_pieces(color, piece).len();
+                        assert_eq!(count, material[color as usize][piece as usize] as u32);
+                    }
+                }
+            }
+        }
+        material[1][Piece::King as usize] = 0;
+        assert!(random_position(&material, Color::White, &mut rng).is_none());
+    }
+}
@@ -0,0 +1,146 @@
+//! [`proptest`](::proptest) strategies for boards, moves, squares, and bitboards.
+//!
+//! All generated boards are valid. Boards shrink toward simpler positions:
+//! playouts shrink toward fewer plies from the standard start position,
+//! and random placements shrink toward less material.
+//! # Examples
+//! ```
+//! # use cozy_chess::*;
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     fn flip_colors_is_an_involution(board in strategy::board()) {
+//!         prop_assert_eq!(board.flip_colors().flip_colors(), board);
+//!     }
+//! }
+//! # flip_colors_is_an_involution();
+//! ```
+
+use ::proptest::prelude::{any, prop_oneof, Just, Strategy};
+use ::proptest::sample::select;
+
+use crate::*;
+use crate::random::*;
+
+/// A strategy for any [`Square`]. Shrinks toward [`Square::A1`].
+pub fn square() -> impl Strategy<Value = Square> {
+    (0..Square::NUM).prop_map(Square::index)
+}
+
+/// A strategy for any [`BitBoard`]. Shrinks toward fewer squares by removing one square at a time.
+pub fn bitboard() -> impl Strategy<Value = BitBoard> {
+    ::proptest::bits::u64::ANY.prop_map(BitBoard)
+}
+
+/// A strategy for boards reached by a random playout of up to `max_plies` plies
+/// from either the standard start position or a Chess960 start position.
+/// Shrinks toward fewer plies from the standard start position.
+pub fn board_from_playout(max_plies: usize) -> impl Strategy<Value = Board> {
+    let start = proptest::option::of(0..960u32);
+    (0..=max_plies, start, any::<u64>()).prop_map(|(plies, start, seed)| {
+        let mut board = match start {
+            Some(scharnagl_number) => Board::chess960_startpos(scharnagl_number),
+            None => Board::default()
+        };
+        random_playout(&mut board, plies, &mut Rng::new(seed));
+        board
+    })
+}
+
+/// A strategy for boards with a random amount of material placed randomly on the board.
+/// The boards have no castle rights or en passant square. Shrinks toward less material.
+pub fn board_from_placement() -> impl Strategy<Value = Board> {
+    let counts = (0..=8u8, 0..=2u8, 0..=2u8, 0..=2u8, 0..=1u8)
+        .prop_map(|(pawns, knights, bishops, rooks, queens)| {
+            [pawns, knights, bishops, rooks, queens, 1]
+        });
+    let side_to_move = prop_oneof![Just(Color::White), Just(Color::Black)];
+    (counts.clone(), counts, side_to_move, any::<u64>()).prop_filter_map(
+        "No valid placement was found",
+        |(white, black, side_to_move, seed)| {
+            random_position(&[white, black], side_to_move, &mut Rng::new(seed))
+        }
+    )
+}
+
+/// A strategy for any valid board, from either [`board_from_playout`] or [`board_from_placement`].
+pub fn board() -> impl Strategy<Value = Board> {
+    prop_oneof![
+        board_from_playout(200),
+        board_from_placement()
+    ]
+}
+
+/// A strategy for the legal moves of a board.
+/// # Panics
+/// Panic if the board has no legal moves.
+pub fn legal_move(board: &Board) -> impl Strategy<Value = Move> {
+    let mut moves = Vec::new();
+    board.generate_moves(|mvs| {
+        moves.extend(mvs);
+        false
+    });
+    select(moves)
+}
+
+/// A strategy for a board with an ongoing game and one of its legal moves.
+pub fn board_and_legal_move() -> impl Strategy<Value = (Board, Move)> {
+    board()
+        .prop_filter("The game is over", |board| board.status() == GameStatus::Ongoing)
+        .prop_flat_map(|board| {
+            let mv = legal_move(&board);
+            (Just(board), mv)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::{prop_assert, prop_assert_eq, proptest};
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn boards_are_valid(board in board()) {
+            prop_assert!(board.validity_check());
+        }
+
+        #[test]
+        fn moves_are_legal((board, mv) in board_and_legal_move()) {
+            prop_assert!(board.is_legal(mv));
+        }
+
+        #[test]
+        fn bitboards_contain_squares(bitboard in bitboard(), square in square()) {
+            prop_assert_eq!(bitboard.has(square), (bitboard | square.bitboard()) == bitboard);
+        }
+    }
+
+    #[test]
+    fn boards_shrink_to_startpos() {
+        use proptest::strategy::ValueTree;
+        use proptest::test_runner::TestRunner;
+
+        let mut runner = TestRunner::deterministic();
+        let mut tree = board_from_playout(50).new_tree(&mut runner).unwrap();
+        while tree.simplify() {}
+        assert_eq!(tree.current(), Board::default());
+    }
+
+    #[test]
+    fn bitboards_shrink_by_squares() {
+        use proptest::strategy::ValueTree;
+        use proptest::test_runner::TestRunner;
+
+        let mut runner = TestRunner::deterministic();
+        let mut tree = bitboard().new_tree(&mut runner).unwrap();
+        let mut prev = tree.current();
+        while tree.simplify() {
+            let current = tree.current();
+            assert!(prev.is_superset(current));
+            assert_eq!(current.len() + 1, prev.len());
+            prev = current;
+        }
+        assert_eq!(tree.current(), BitBoard::EMPTY);
+    }
+}
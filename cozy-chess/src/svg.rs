@@ -0,0 +1,314 @@
+//! SVG rendering of boards. Requires the `std` feature.
+//!
+//! [`BoardSvg`] renders a [`Board`] as a self-contained SVG image with built-in piece glyphs,
+//! so it can be embedded in HTML or written to a file without any external resources.
+
+use std::fmt::{Display, Formatter};
+
+use crate::*;
+
+const SQUARE_SIZE: f32 = 45.0;
+const MARGIN: f32 = 20.0;
+
+const LIGHT_SQUARE: &str = "#f0d9b5";
+const DARK_SQUARE: &str = "#b58863";
+const LAST_MOVE: &str = "#cdd26a";
+const HIGHLIGHT: &str = "#3c8dde";
+const ARROW: &str = "#15781b";
+
+// Glyphs are drawn in a 45x45 box. Shapes are filled with the piece color and
+// details that should contrast with the fill use `currentColor`.
+const PAWN: &str = concat!(
+    r#"<circle cx="22.5" cy="13" r="4.5"/>"#,
+    r#"<path d="M18 21.5H27L30 34H15Z"/>"#,
+    r#"<path d="M12 37V34H33V37Z"/>"#
+);
+const KNIGHT: &str = concat!(
+    r#"<path d="M14 37H33V34C33 26 32 16 24 11L22 8L20 12C16 13 12 18 11 22L12.5 25L16 23.5L20 22C19 26 15 29 14 34Z"/>"#,
+    r#"<circle cx="18" cy="16" r="1.2" fill="currentColor" stroke="none"/>"#
+);
+const BISHOP: &str = concat!(
+    r#"<circle cx="22.5" cy="9" r="2.5"/>"#,
+    r#"<path d="M15 32C15 24 22.5 12 22.5 12C22.5 12 30 24 30 32Z"/>"#,
+    r#"<path d="M12 37V34H33V37Z"/>"#,
+    r#"<path d="M20 23L25 19" fill="none" stroke="currentColor"/>"#
+);
+const ROOK: &str = concat!(
+    r#"<path d="M11 37H34V33H31V19H34V12H30V15H25.5V12H19.5V15H15V12H11V19H14V33H11Z"/>"#,
+    r#"<path d="M14 19H31M14 33H31" fill="none" stroke="currentColor"/>"#
+);
+const QUEEN: &str = concat!(
+    r#"<path d="M12 32L9 14L16 24L18 11L22.5 22L27 11L29 24L36 14L33 32Z"/>"#,
+    r#"<circle cx="9" cy="12" r="2"/><circle cx="18" cy="9" r="2"/>"#,
+    r#"<circle cx="27" cy="9" r="2"/><circle cx="36" cy="12" r="2"/>"#,
+    r#"<path d="M11 37V34H34V37Z"/>"#
+);
+const KING: &str = concat!(
+    r#"<path d="M22.5 6V16M18.5 10H26.5" fill="none"/>"#,
+    r#"<path d="M12 32C8 22 16 17 22.5 25C29 17 37 22 33 32Z"/>"#,
+    r#"<path d="M11 37V34H34V37Z"/>"#
+);
+const GLYPHS: [&str; Piece::NUM] = [PAWN, KNIGHT, BISHOP, ROOK, QUEEN, KING];
+
+fn glyph_id(piece: Piece, color: Color) -> String {
+    format!("{:?}-{:?}", color, piece).to_ascii_lowercase()
+}
+
+/// An SVG image of a [`Board`].
+/// Kings in check are always highlighted. Render the image with its [`Display`] implementation.
+/// # Examples
+/// ```
+/// # use cozy_chess::*;
+/// # use cozy_chess::svg::*;
+/// let mut board = Board::default();
+/// let mv = "e2e4".parse().unwrap();
+/// board.play(mv);
+/// let svg = BoardSvg::new(&board)
+///     .flipped(true)
+///     .last_move(mv)
+///     .arrow(Square::E7, Square::E5)
+///     .highlight(Square::D5.bitboard())
+///     .to_string();
+/// assert!(svg.starts_with("<svg"));
+/// assert!(svg.ends_with("</svg>"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct BoardSvg<'b> {
+    board: &'b Board,
+    flipped: bool,
+    coordinates: bool,
+    last_move: Option<Move>,
+    highlights: BitBoard,
+    arrows: Vec<(Square, Square)>
+}
+
+impl<'b> BoardSvg<'b> {
+    /// Render a board from white's perspective with coordinates.
+    pub fn new(board: &'b Board) -> Self {
+        Self {
+            board,
+            flipped: false,
+            coordinates: true,
+            last_move: None,
+            highlights: BitBoard::EMPTY,
+            arrows: Vec::new()
+        }
+    }
+
+    /// Render the board from black's perspective.
+    pub fn flipped(mut self, flipped: bool) -> Self {
+        self.flipped = flipped;
+        self
+    }
+
+    /// Draw file and rank labels around the board.
+    pub fn coordinates(mut self, coordinates: bool) -> Self {
+        self.coordinates = coordinates;
+        self
+    }
+
+    /// Highlight the source and destination squares of the last move.
+    pub fn last_move(mut self, mv: Move) -> Self {
+        self.last_move = Some(mv);
+        self
+    }
+
+    /// Highlight some squares. Highlights from multiple calls are combined.
+    pub fn highlight(mut self, squares: BitBoard) -> Self {
+        self.highlights |= squares;
+        self
+    }
+
+    /// Draw an arrow between two squares. An arrow from a square to itself is drawn as a circle.
+    pub fn arrow(mut self, from: Square, to: Square) -> Self {
+        self.arrows.push((from, to));
+        self
+    }
+
+    fn margin(&self) -> f32 {
+        if self.coordinates {
+            MARGIN
+        } else {
+            0.0
+        }
+    }
+
+    /// The top left corner of a square.
+    fn corner(&self, square: Square) -> (f32, f32) {
+        let (x, y) = if self.flipped {
+            (7 - square.file() as usize, square.rank() as usize)
+        } else {
+            (square.file() as usize, 7 - square.rank() as usize)
+        };
+        let margin = self.margin();
+        (margin + x as f32 * SQUARE_SIZE, margin + y as f32 * SQUARE_SIZE)
+    }
+
+    fn center(&self, square: Square) -> (f32, f32) {
+        let (x, y) = self.corner(square);
+        (x + SQUARE_SIZE / 2.0, y + SQUARE_SIZE / 2.0)
+    }
+
+    fn write_square(&self, f: &mut Formatter<'_>, square: Square, fill: &str, opacity: f32) -> std::fmt::Result {
+        let (x, y) = self.corner(square);
+        write!(
+            f,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}" fill-opacity="{}"/>"#,
+            x, y, SQUARE_SIZE, SQUARE_SIZE, fill, opacity
+        )
+    }
+
+    fn write_arrow(&self, f: &mut Formatter<'_>, from: Square, to: Square) -> std::fmt::Result {
+        let (x1, y1) = self.center(from);
+        let (x2, y2) = self.center(to);
+        let width = SQUARE_SIZE * 0.15;
+        if from == to {
+            return write!(
+                f,
+                r#"<circle cx="{}" cy="{}" r="{}" fill="none" stroke="{}" stroke-width="{}" opacity="0.8"/>"#,
+                x1, y1, (SQUARE_SIZE - width) / 2.0, ARROW, width
+            );
+        }
+        let (dx, dy) = (x2 - x1, y2 - y1);
+        let length = (dx * dx + dy * dy).sqrt();
+        let (ux, uy) = (dx / length, dy / length);
+        let head_length = SQUARE_SIZE * 0.35;
+        let head_width = SQUARE_SIZE * 0.4;
+        let (bx, by) = (x2 - ux * head_length, y2 - uy * head_length);
+        let (px, py) = (-uy * head_width / 2.0, ux * head_width / 2.0);
+        write!(
+            f,
+            r#"<line x1="{:.2}" y1="{:.2}" x2="{:.2}" y2="{:.2}" stroke="{}" stroke-width="{}" stroke-linecap="round" opacity="0.8"/>"#,
+            x1, y1, bx, by, ARROW, width
+        )?;
+        write!(
+            f,
+            r#"<polygon points="{:.2},{:.2} {:.2},{:.2} {:.2},{:.2}" fill="{}" opacity="0.8"/>"#,
+            x2, y2, bx + px, by + py, bx - px, by - py, ARROW
+        )
+    }
+}
+
+impl Display for BoardSvg<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let size = SQUARE_SIZE * 8.0 + self.margin() * 2.0;
+        write!(
+            f,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" viewBox="0 0 {0} {0}" width="{0}" height="{0}">"#,
+            size
+        )?;
+
+        write!(f, "<defs>")?;
+        for &color in &Color::ALL {
+            let (fill, detail) = match color {
+                Color::White => ("#fff", "#000"),
+                Color::Black => ("#000", "#fff")
+            };
+            for &piece in &Piece::ALL {
+                write!(
+                    f,
+                    r##"<g id="{}" fill="{}" stroke="#000" color="{}" stroke-width="1.5" stroke-linejoin="round" stroke-linecap="round">{}</g>"##,
+                    glyph_id(piece, color), fill, detail, GLYPHS[piece as usize]
+                )?;
+            }
+        }
+        write!(
+            f,
+            r##"<radialGradient id="check"><stop offset="0%" stop-color="#f00"/><stop offset="50%" stop-color="#e70000"/><stop offset="100%" stop-color="#9e0000" stop-opacity="0"/></radialGradient>"##
+        )?;
+        write!(f, "</defs>")?;
+
+        if self.coordinates {
+            write!(f, r##"<rect width="{0}" height="{0}" fill="#212121"/>"##, size)?;
+        }
+        for &square in &Square::ALL {
+            let light = (square.file() as usize + square.rank() as usize) % 2 == 1;
+            let fill = if light {
+                LIGHT_SQUARE
+            } else {
+                DARK_SQUARE
+            };
+            self.write_square(f, square, fill, 1.0)?;
+        }
+        if let Some(mv) = self.last_move {
+            self.write_square(f, mv.from, LAST_MOVE, 0.8)?;
+            self.write_square(f, mv.to, LAST_MOVE, 0.8)?;
+        }
+        for square in self.highlights {
+            self.write_square(f, square, HIGHLIGHT, 0.5)?;
+        }
+        if !self.board.checkers().is_empty() {
+            let king = self.board.king(self.board.side_to_move());
+            self.write_square(f, king, "url(#check)", 1.0)?;
+        }
+
+        if self.coordinates {
+            let style = r##"fill="#e5e5e5" font-family="sans-serif" font-size="14" text-anchor="middle" dominant-baseline="central""##;
+            for &file in &File::ALL {
+                let (x, _) = self.center(Square::new(file, Rank::First));
+                let y = MARGIN * 1.5 + SQUARE_SIZE * 8.0;
+                write!(f, r#"<text x="{}" y="{}" {}>{}</text>"#, x, y, style, char::from(file))?;
+            }
+            for &rank in &Rank::ALL {
+                let (_, y) = self.center(Square::new(File::A, rank));
+                write!(f, r#"<text x="{}" y="{}" {}>{}</text>"#, MARGIN / 2.0, y, style, char::from(rank))?;
+            }
+        }
+
+        for &color in &Color::ALL {
+            for &piece in &Piece::ALL {
+                for square in self.board.colored_pieces(color, piece) {
+                    let (x, y) = self.corner(square);
+                    let id = glyph_id(piece, color);
+                    write!(f, r##"<use href="#{0}" xlink:href="#{0}" x="{1}" y="{2}"/>"##, id, x, y)?;
+                }
+            }
+        }
+
+        for &(from, to) in &self.arrows {
+            self.write_arrow(f, from, to)?;
+        }
+        write!(f, "</svg>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_pieces_and_highlights() {
+        let board = Board::default();
+        let svg = BoardSvg::new(&board).to_string();
+        assert_eq!(svg.matches("<use ").count(), 32);
+        assert_eq!(svg.matches("<text ").count(), 16);
+        assert!(!svg.contains("url(#check)\" fill-opacity"));
+
+        let svg = BoardSvg::new(&board)
+            .coordinates(false)
+            .highlight(Rank::Fourth.bitboard())
+            .arrow(Square::E2, Square::E4)
+            .arrow(Square::D4, Square::D4)
+            .to_string();
+        assert_eq!(svg.matches("<text ").count(), 0);
+        assert_eq!(svg.matches(HIGHLIGHT).count(), 8);
+        assert_eq!(svg.matches("<polygon ").count(), 1);
+        assert_eq!(svg.matches("<circle cx=\"157.5\" cy=\"202.5\"").count(), 1);
+    }
+
+    #[test]
+    fn flipped_board() {
+        let board = Board::default();
+        let svg = BoardSvg::new(&board).coordinates(false).to_string();
+        assert!(svg.contains(r##"<use href="#white-rook" xlink:href="#white-rook" x="0" y="315"/>"##));
+        let svg = BoardSvg::new(&board).coordinates(false).flipped(true).to_string();
+        assert!(svg.contains(r##"<use href="#white-rook" xlink:href="#white-rook" x="315" y="0"/>"##));
+    }
+
+    #[test]
+    fn highlights_check() {
+        let board: Board = "rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3".parse().unwrap();
+        let svg = BoardSvg::new(&board).coordinates(false).to_string();
+        assert!(svg.contains(r#"<rect x="180" y="315" width="45" height="45" fill="url(#check)""#));
+    }
+}
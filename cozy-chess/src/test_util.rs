@@ -0,0 +1,75 @@
+//! Helpers shared by the tests.
+
+use crate::*;
+
+/// Count the positions reachable in exactly `depth` plies.
+pub fn perft<P: Position>(position: &P, depth: u8) -> u64 {
+    let mut nodes = 0;
+    match depth {
+        0 => nodes += 1,
+        1 => {
+            position.generate_moves(|moves| {
+                nodes += moves.len() as u64;
+                false
+            });
+        }
+        _ => {
+            position.generate_moves(|moves| {
+                for mv in moves {
+                    let mut position = position.clone();
+                    position.play_unchecked(mv);
+                    let child_nodes = perft(&position, depth - 1);
+                    nodes += child_nodes;
+                }
+                false
+            });
+        }
+    }
+    nodes
+}
+
+/// The benchmark positions, followed by positions with castling, en passant,
+/// promotions and Chess960 castling close to the root.
+pub fn test_positions() -> impl Iterator<Item = Board> {
+    const POSITIONS: &[&str] = &[
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        "rkb2bnr/pp2pppp/2p1n3/3p4/q2P4/5NP1/PPP1PP1P/RKBNQBR1 w Aha - 0 9"
+    ];
+    include_str!("board/test_data/bench_positions.fens")
+        .lines()
+        .chain(POSITIONS.iter().copied())
+        .map(|fen| fen.parse().unwrap())
+}
+
+/// Call `f` with every position within `depth - 1` plies of `board`,
+/// each of its legal moves, and the position after that move.
+pub fn for_each_move(board: &Board, depth: u8, f: &mut impl FnMut(&Board, Move, &Board)) {
+    board.generate_moves(|moves| {
+        for mv in moves {
+            let mut child = board.clone();
+            child.play_unchecked(mv);
+            f(board, mv, &child);
+            if depth > 1 {
+                for_each_move(&child, depth - 1, f);
+            }
+        }
+        false
+    });
+}
+
+/// Call `f` with `board` and every position within `depth` plies of it.
+pub fn for_each_position(board: &Board, depth: u8, f: &mut impl FnMut(&Board)) {
+    f(board);
+    if depth > 0 {
+        board.generate_moves(|moves| {
+            for mv in moves {
+                let mut child = board.clone();
+                child.play_unchecked(mv);
+                for_each_position(&child, depth - 1, f);
+            }
+            false
+        });
+    }
+}
@@ -0,0 +1,542 @@
+use core::fmt::{Display, Formatter};
+
+use crate::*;
+
+use super::parse_san_move;
+
+/// The side of the board a file belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Wing {
+    Queen,
+    King
+}
+
+impl Wing {
+    fn of(file: File) -> Self {
+        if file <= File::D {
+            Self::Queen
+        } else {
+            Self::King
+        }
+    }
+
+    fn bitboard(self) -> BitBoard {
+        match self {
+            Self::Queen => File::A.bitboard() | File::B.bitboard() | File::C.bitboard() | File::D.bitboard(),
+            Self::King => File::E.bitboard() | File::F.bitboard() | File::G.bitboard() | File::H.bitboard()
+        }
+    }
+}
+
+/// The files named after the piece that starts on them, or [`None`] for the queen and king files.
+fn file_piece(file: File) -> Option<Piece> {
+    match file {
+        File::A | File::H => Some(Piece::Rook),
+        File::B | File::G => Some(Piece::Knight),
+        File::C | File::F => Some(Piece::Bishop),
+        File::D | File::E => None
+    }
+}
+
+/// The files named by a wing and the piece that starts on them, like `QB` for the C file.
+/// A wing alone names the queen or king file, and a piece alone names both of its files.
+fn named_files(wing: Option<Wing>, piece: Option<Piece>) -> Option<BitBoard> {
+    let files = match piece {
+        Some(piece) => File::ALL.iter()
+            .filter(|&&file| file_piece(file) == Some(piece))
+            .fold(BitBoard::EMPTY, |files, &file| files | file.bitboard()),
+        None => match wing? {
+            Wing::Queen => File::D.bitboard(),
+            Wing::King => File::E.bitboard()
+        }
+    };
+    match wing {
+        Some(wing) => Some(files & wing.bitboard()),
+        None => Some(files)
+    }
+}
+
+/// A piece, optionally qualified by its wing and, for pawns, its file. For example `N`, `QR`, or `KBP`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PieceName {
+    wing: Option<Wing>,
+    file_piece: Option<Piece>,
+    piece: Piece
+}
+
+impl PieceName {
+    /// The squares a piece with this name can be on.
+    fn squares(&self) -> BitBoard {
+        if self.piece == Piece::Pawn {
+            named_files(self.wing, self.file_piece).unwrap_or(BitBoard::FULL)
+        } else {
+            self.wing.map_or(BitBoard::FULL, Wing::bitboard)
+        }
+    }
+
+    /// The names of a piece on a square, from least to most specific.
+    fn candidates(piece: Piece, square: Square) -> [Self; 3] {
+        let plain = Self {
+            wing: None,
+            file_piece: None,
+            piece
+        };
+        let wing = Some(Wing::of(square.file()));
+        if piece != Piece::Pawn {
+            return [plain, Self { wing, ..plain }, Self { wing, ..plain }];
+        }
+        let file_piece = file_piece(square.file());
+        [plain, Self { file_piece, ..plain }, Self { wing, file_piece, piece }]
+    }
+}
+
+/// A square relative to the side to move, like `K4`, `B3`, or `QB3`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SquareName {
+    wing: Option<Wing>,
+    file_piece: Option<Piece>,
+    rank: Rank
+}
+
+impl SquareName {
+    fn squares(&self, color: Color) -> BitBoard {
+        let files = named_files(self.wing, self.file_piece).unwrap_or(BitBoard::EMPTY);
+        files & self.rank.relative_to(color).bitboard()
+    }
+
+    /// The names of a square, from least to most specific.
+    fn candidates(square: Square, color: Color) -> [Self; 2] {
+        let full = Self {
+            wing: Some(Wing::of(square.file())),
+            file_piece: file_piece(square.file()),
+            rank: square.rank().relative_to(color)
+        };
+        match full.file_piece {
+            Some(_) => [Self { wing: None, ..full }, full],
+            None => [full, full]
+        }
+    }
+}
+
+/// What a move goes to or captures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Target {
+    Square(SquareName),
+    Piece(PieceName, Option<SquareName>)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct DescriptiveMove {
+    piece: PieceName,
+    from: Option<SquareName>,
+    captures: bool,
+    target: Target,
+    promotion: Option<Piece>
+}
+
+impl DescriptiveMove {
+    /// Resolve the move against the legal moves, failing if there are none or several.
+    fn resolve(&self, board: &impl Position) -> Result<Move, MoveParseError> {
+        let color = board.side_to_move();
+        let mut from_mask = board.colored_pieces(color, self.piece.piece) & self.piece.squares();
+        if let Some(from) = self.from {
+            from_mask &= from.squares(color);
+        }
+        let mut found = None;
+        let mut ambiguous = false;
+        board.generate_moves_for(from_mask, |mvs| {
+            for mv in mvs {
+                if board.is_castle(mv) || mv.promotion != self.promotion || board.is_capture(mv) != self.captures {
+                    continue;
+                }
+                // Option::is_none_or needs Rust 1.82.
+                #[allow(clippy::unnecessary_map_or)]
+                let matches = match self.target {
+                    Target::Square(square) => square.squares(color).has(mv.to),
+                    Target::Piece(piece, square) => {
+                        let captured_square = if board.is_en_passant(mv) {
+                            Square::new(mv.to.file(), mv.from.rank())
+                        } else {
+                            mv.to
+                        };
+                        board.captured_piece(mv) == Some(piece.piece)
+                            && piece.squares().has(captured_square)
+                            && square.map_or(true, |square| square.squares(color).has(mv.to))
+                    }
+                };
+                if matches {
+                    ambiguous |= found.is_some();
+                    found = Some(mv);
+                }
+            }
+            ambiguous
+        });
+        match found {
+            Some(mv) if !ambiguous => Ok(mv),
+            _ => Err(MoveParseError)
+        }
+    }
+}
+
+fn piece_letter(piece: Piece) -> char {
+    char::from(piece).to_ascii_uppercase()
+}
+
+impl Display for PieceName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self.wing {
+            Some(Wing::Queen) => write!(f, "Q")?,
+            Some(Wing::King) => write!(f, "K")?,
+            None => {}
+        }
+        if let Some(piece) = self.file_piece {
+            write!(f, "{}", piece_letter(piece))?;
+        }
+        write!(f, "{}", piece_letter(self.piece))
+    }
+}
+
+impl Display for SquareName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self.wing {
+            Some(Wing::Queen) => write!(f, "Q")?,
+            Some(Wing::King) => write!(f, "K")?,
+            None => {}
+        }
+        if let Some(piece) = self.file_piece {
+            write!(f, "{}", piece_letter(piece))?;
+        }
+        write!(f, "{}", self.rank as usize + 1)
+    }
+}
+
+impl Display for DescriptiveMove {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.piece)?;
+        if let Some(from) = self.from {
+            write!(f, "({})", from)?;
+        }
+        match self.target {
+            Target::Square(square) if self.captures => write!(f, "x{}", square)?,
+            Target::Square(square) => write!(f, "-{}", square)?,
+            Target::Piece(piece, None) => write!(f, "x{}", piece)?,
+            Target::Piece(piece, Some(square)) => write!(f, "x{}/{}", piece, square)?
+        }
+        if let Some(promotion) = self.promotion {
+            write!(f, "={}", piece_letter(promotion))?;
+        }
+        Ok(())
+    }
+}
+
+/// A reader for the tokens of descriptive notation.
+struct Reader<'s>(&'s str);
+
+impl Reader<'_> {
+    fn eat(&mut self, prefix: &str) -> bool {
+        match self.0.strip_prefix(prefix) {
+            Some(rest) => {
+                self.0 = rest;
+                true
+            }
+            None => false
+        }
+    }
+
+    /// Read a piece letter. Knights may be written as `N` or `Kt`.
+    fn letter(&mut self) -> Option<Piece> {
+        const LETTERS: [(&str, Piece); 7] = [
+            ("Kt", Piece::Knight), ("N", Piece::Knight), ("P", Piece::Pawn), ("B", Piece::Bishop),
+            ("R", Piece::Rook), ("Q", Piece::Queen), ("K", Piece::King)
+        ];
+        LETTERS.iter().find(|(letter, _)| self.eat(letter)).map(|&(_, piece)| piece)
+    }
+
+    /// Read up to three piece letters and an optional rank.
+    fn name(&mut self) -> ([Option<Piece>; 3], Option<Rank>) {
+        let mut letters = [None; 3];
+        for letter in &mut letters {
+            *letter = self.letter();
+            if letter.is_none() {
+                break;
+            }
+        }
+        let rank = self.0.chars().next()
+            .and_then(|c| c.to_digit(10))
+            .filter(|rank| (1..=8).contains(rank))
+            .map(|rank| Rank::index(rank as usize - 1));
+        if rank.is_some() {
+            self.0 = &self.0[1..];
+        }
+        (letters, rank)
+    }
+
+    fn piece_name(&mut self) -> Option<PieceName> {
+        match self.name() {
+            (letters, None) => piece_name(letters),
+            _ => None
+        }
+    }
+
+    fn square_name(&mut self) -> Option<SquareName> {
+        match self.name() {
+            (letters, Some(rank)) => square_name(letters, rank),
+            _ => None
+        }
+    }
+}
+
+fn wing(piece: Piece) -> Option<Wing> {
+    match piece {
+        Piece::Queen => Some(Wing::Queen),
+        Piece::King => Some(Wing::King),
+        _ => None
+    }
+}
+
+fn is_file_piece(piece: Piece) -> bool {
+    matches!(piece, Piece::Rook | Piece::Knight | Piece::Bishop)
+}
+
+fn piece_name(letters: [Option<Piece>; 3]) -> Option<PieceName> {
+    let name = |wing, file_piece, piece| Some(PieceName { wing, file_piece, piece });
+    match letters {
+        [Some(piece), None, None] => name(None, None, piece),
+        [Some(Piece::Pawn), ..] => None,
+        [Some(w), Some(Piece::Pawn), None] if wing(w).is_some() => name(wing(w), None, Piece::Pawn),
+        [Some(p), Some(Piece::Pawn), None] if is_file_piece(p) => name(None, Some(p), Piece::Pawn),
+        [Some(w), Some(piece), None] if wing(w).is_some() => name(wing(w), None, piece),
+        [Some(w), Some(p), Some(Piece::Pawn)] if wing(w).is_some() && is_file_piece(p) => {
+            name(wing(w), Some(p), Piece::Pawn)
+        }
+        _ => None
+    }
+}
+
+fn square_name(letters: [Option<Piece>; 3], rank: Rank) -> Option<SquareName> {
+    let name = |wing, file_piece| Some(SquareName { wing, file_piece, rank });
+    match letters {
+        [Some(w), None, None] if wing(w).is_some() => name(wing(w), None),
+        [Some(p), None, None] if is_file_piece(p) => name(None, Some(p)),
+        [Some(w), Some(p), None] if wing(w).is_some() && is_file_piece(p) => name(wing(w), Some(p)),
+        _ => None
+    }
+}
+
+/// Strip check markers, annotations, and the en passant marker.
+fn strip_suffixes(mut mv: &str) -> &str {
+    const SUFFIXES: [&str; 11] = ["+", "#", "!", "?", "dis ch", "dbl ch", "ch", "mate", "e.p.", "ep", "."];
+    loop {
+        mv = mv.trim_end();
+        match SUFFIXES.iter().find_map(|suffix| mv.strip_suffix(suffix)) {
+            Some(rest) => mv = rest,
+            None => return mv
+        }
+    }
+}
+
+fn parse(mv: &str) -> Option<DescriptiveMove> {
+    let mut reader = Reader(mv);
+    let piece = reader.piece_name()?;
+    let from = if reader.eat("(") {
+        let from = reader.square_name()?;
+        Some(reader.eat(")").then_some(from)?)
+    } else if reader.eat("/") {
+        Some(reader.square_name()?)
+    } else {
+        None
+    };
+    let captures = if reader.eat("-") {
+        false
+    } else if reader.eat("x") || reader.eat(":") {
+        true
+    } else {
+        return None;
+    };
+    let target = match reader.name() {
+        (letters, Some(rank)) => Target::Square(square_name(letters, rank)?),
+        (letters, None) if captures => {
+            let piece = piece_name(letters)?;
+            let mut square = None;
+            // Either a square qualifying the captured piece or a promotion
+            let rest = reader.0;
+            if reader.eat("/") {
+                square = reader.square_name();
+                if square.is_none() {
+                    reader.0 = rest;
+                }
+            }
+            Target::Piece(piece, square)
+        }
+        _ => return None
+    };
+    let promotion = if reader.eat("=") || reader.eat("/") {
+        Some(reader.letter()?)
+    } else if reader.eat("(") {
+        let promotion = reader.letter()?;
+        reader.eat(")").then_some(promotion)
+    } else {
+        None
+    };
+    if !reader.0.is_empty() {
+        return None;
+    }
+    Some(DescriptiveMove {
+        piece,
+        from,
+        captures,
+        target,
+        promotion
+    })
+}
+
+/// Parses an English descriptive notation move into a [`Move`].
+///
+/// Files are named after the pieces that start on them, such as `QB` for the C file or `K`
+/// for the E file, and ranks are counted from the side to move. A file may be given without its
+/// wing, like `B3`, as long as only one legal move matches. Captures may name the captured piece,
+/// like `PxP` or `NxQB3`. Knights may be written as `N` or `Kt`, promotions as `=Q`, `/Q`, or `(Q)`,
+/// and the moving piece may be disambiguated with its square, like `R(Q1)-Q3`. Check markers such
+/// as `ch` and `+`, annotations, and `e.p.` are ignored. The returned move is always legal.
+///
+/// # Examples
+///
+/// ```
+/// # use cozy_chess::*;
+/// # use cozy_chess::util::*;
+/// let mut board = Board::default();
+/// assert_eq!(parse_descriptive_move(&board, "P-K4").unwrap(), "e2e4".parse().unwrap());
+/// assert_eq!(parse_descriptive_move(&board, "N-KB3").unwrap(), "g1f3".parse().unwrap());
+/// assert!(parse_descriptive_move(&board, "N-B3").is_err());
+/// board.play("e2e4".parse().unwrap());
+/// assert_eq!(parse_descriptive_move(&board, "P-K4").unwrap(), "e7e5".parse().unwrap());
+/// ```
+pub fn parse_descriptive_move(board: &impl Position, mv: &str) -> Result<Move, MoveParseError> {
+    let mv = strip_suffixes(mv.trim());
+    if mv.starts_with(['O', '0']) {
+        return parse_san_move(board, mv);
+    }
+    parse(mv).ok_or(MoveParseError)?.resolve(board)
+}
+
+/// Returns an object that allows printing a [`Move`] in English descriptive notation.
+/// See [`parse_descriptive_move`] for the format.
+///
+/// The shortest unambiguous form is used. Check is written as `ch` and checkmate as `mate`.
+///
+/// # Errors
+/// This errors if the move is illegal.
+///
+/// # Examples
+///
+/// ```
+/// # use cozy_chess::*;
+/// # use cozy_chess::util::*;
+/// let board: Board = "r1bqkbnr/pppp1ppp/2n5/4p3/3PP3/5N2/PPP2PPP/RNBQKB1R b KQkq - 0 3"
+///     .parse().unwrap();
+/// let mv: Move = "e5d4".parse().unwrap();
+/// assert_eq!(format!("{}", display_descriptive_move(&board, mv).unwrap()), "PxP");
+/// let mv: Move = "g8f6".parse().unwrap();
+/// assert_eq!(format!("{}", display_descriptive_move(&board, mv).unwrap()), "N-B3");
+/// let mv: Move = "f8b4".parse().unwrap();
+/// assert_eq!(format!("{}", display_descriptive_move(&board, mv).unwrap()), "B-N5ch");
+/// let mv: Move = "e8g8".parse().unwrap();
+/// assert!(display_descriptive_move(&board, mv).is_err());
+/// ```
+pub fn display_descriptive_move(board: &impl Position, mv: Move) -> Result<impl Display, IllegalMoveError> {
+    let mut after_board = board.clone();
+    after_board.try_play(mv)?;
+
+    let check = !after_board.checkers().is_empty();
+    let checkmate = check && !after_board.generate_moves(|_| true);
+    let castles = board.is_castle(mv);
+    let color = board.side_to_move();
+    let piece = board.moved_piece(mv).unwrap();
+    let captured = board.captured_piece(mv);
+
+    let kind = if castles {
+        if mv.from.file() < mv.to.file() {
+            DescriptiveKind::ShortCastles
+        } else {
+            DescriptiveKind::LongCastles
+        }
+    } else {
+        let captured_square = if board.is_en_passant(mv) {
+            Square::new(mv.to.file(), mv.from.rank())
+        } else {
+            mv.to
+        };
+        let to_names = SquareName::candidates(mv.to, color);
+        let [_, full_from] = SquareName::candidates(mv.from, color);
+        let captured_names = captured.map(|captured| PieceName::candidates(captured, captured_square));
+        let targets = captured_names.into_iter().flatten()
+            .map(|piece| Target::Piece(piece, None))
+            .chain(to_names.iter().map(|&square| Target::Square(square)));
+        let mut found = None;
+        'search: for from in [None, Some(full_from)] {
+            for piece in PieceName::candidates(piece, mv.from) {
+                for target in targets.clone() {
+                    let candidate = DescriptiveMove {
+                        piece,
+                        from,
+                        captures: captured.is_some(),
+                        target,
+                        promotion: mv.promotion
+                    };
+                    if candidate.resolve(board).ok() == Some(mv) {
+                        found = Some(candidate);
+                        break 'search;
+                    }
+                }
+            }
+        }
+        // Fall back to naming everything in full if no shorter form resolves.
+        let [.., full_piece] = PieceName::candidates(piece, mv.from);
+        let [_, full_to] = to_names;
+        let full = DescriptiveMove {
+            piece: full_piece,
+            from: Some(full_from),
+            captures: captured.is_some(),
+            target: match captured_names {
+                Some([.., full_captured]) => Target::Piece(full_captured, Some(full_to)),
+                None => Target::Square(full_to)
+            },
+            promotion: mv.promotion
+        };
+        DescriptiveKind::Move(found.unwrap_or(full))
+    };
+
+    Ok(DescriptiveDisplay {
+        kind,
+        check,
+        checkmate
+    })
+}
+
+enum DescriptiveKind {
+    Move(DescriptiveMove),
+    ShortCastles,
+    LongCastles
+}
+
+struct DescriptiveDisplay {
+    kind: DescriptiveKind,
+    check: bool,
+    checkmate: bool
+}
+
+impl Display for DescriptiveDisplay {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self.kind {
+            DescriptiveKind::Move(descriptive) => write!(f, "{}", descriptive)?,
+            DescriptiveKind::ShortCastles => write!(f, "O-O")?,
+            DescriptiveKind::LongCastles => write!(f, "O-O-O")?
+        }
+        if self.checkmate {
+            write!(f, "mate")?;
+        } else if self.check {
+            write!(f, "ch")?;
+        }
+        Ok(())
+    }
+}
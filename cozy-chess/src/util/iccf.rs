@@ -0,0 +1,103 @@
+use core::fmt::Display;
+
+use crate::*;
+
+use super::{from_standard_castle, to_standard_castle};
+
+const PROMOTIONS: [Piece; 4] = [Piece::Queen, Piece::Rook, Piece::Bishop, Piece::Knight];
+
+/// Parses an ICCF numeric notation move into a [`Move`].
+///
+/// ICCF numeric notation gives the file and rank of the source and destination squares
+/// as digits from 1 to 8, like `5254` for `e2e4`. A fifth digit gives the promotion piece,
+/// with 1 for a queen, 2 for a rook, 3 for a bishop, and 4 for a knight. Castling is written
+/// as the king's move, like `5171`. In Chess960, if the king does not start on the E file,
+/// castling is written as the king moving to its rook's square instead, like `2111` for a
+/// king on B1 castling with a rook on A1. The returned move is always legal.
+///
+/// # Examples
+///
+/// ```
+/// # use cozy_chess::*;
+/// # use cozy_chess::util::*;
+/// let board: Board = "rnbqkb1r/ppp2ppp/4pn2/3p4/8/5NP1/PPPPPPBP/RNBQK2R w KQkq - 0 4"
+///     .parse().unwrap();
+/// assert_eq!(parse_iccf_move(&board, "2133").unwrap(), "b1c3".parse().unwrap());
+/// assert_eq!(parse_iccf_move(&board, "5171").unwrap(), "e1h1".parse().unwrap());
+/// assert!(parse_iccf_move(&board, "5254").is_ok());
+/// assert!(parse_iccf_move(&board, "5255").is_err());
+///
+/// let board: Board = "rk4r1/8/8/8/8/8/8/RK4R1 w GAga - 0 1".parse().unwrap();
+/// assert_eq!(parse_iccf_move(&board, "2111").unwrap(), "b1a1".parse().unwrap());
+/// assert_eq!(parse_iccf_move(&board, "2131").unwrap(), "b1c1".parse().unwrap());
+/// ```
+pub fn parse_iccf_move(board: &impl Position, mv: &str) -> Result<Move, MoveParseError> {
+    let mut digits = mv.chars().map(|c| match c.to_digit(10) {
+        Some(digit @ 1..=8) => Ok(digit as usize - 1),
+        _ => Err(MoveParseError)
+    });
+    let mut square = || -> Result<Square, MoveParseError> {
+        let file = digits.next().ok_or(MoveParseError)??;
+        let rank = digits.next().ok_or(MoveParseError)??;
+        Ok(Square::new(File::index(file), Rank::index(rank)))
+    };
+    let from = square()?;
+    let to = square()?;
+    let promotion = match digits.next() {
+        Some(index) => Some(*PROMOTIONS.get(index?).ok_or(MoveParseError)?),
+        None => None
+    };
+    if digits.next().is_some() {
+        return Err(MoveParseError);
+    }
+
+    let mv = from_standard_castle(board, Move { from, to, promotion });
+    if !board.is_legal(mv) {
+        return Err(MoveParseError);
+    }
+    Ok(mv)
+}
+
+/// Returns an object that allows printing a [`Move`] in ICCF numeric notation.
+/// See [`parse_iccf_move`] for the format.
+///
+/// # Examples
+///
+/// ```
+/// # use cozy_chess::*;
+/// # use cozy_chess::util::*;
+/// let board: Board = "3k2n1/7P/Q3p3/4BPp1/Q1Q4q/8/5B2/R3K2R w KQ g6 0 1"
+///     .parse().unwrap();
+/// let mv: Move = "h7g8n".parse().unwrap();
+/// assert_eq!(format!("{}", display_iccf_move(&board, mv)), "87784");
+/// let mv: Move = "e1a1".parse().unwrap();
+/// assert_eq!(format!("{}", display_iccf_move(&board, mv)), "5131");
+///
+/// let board: Board = "rk4r1/8/8/8/8/8/8/RK4R1 w GAga - 0 1".parse().unwrap();
+/// let mv: Move = "b1g1".parse().unwrap();
+/// assert_eq!(format!("{}", display_iccf_move(&board, mv)), "2171");
+/// ```
+pub fn display_iccf_move(board: &impl Position, mv: Move) -> impl Display {
+    // The king's destination only identifies a castle if the king starts on the E file.
+    // Otherwise, a king moving to the G or C file could also be a normal king move.
+    if board.king(board.side_to_move()).file() == File::E {
+        IccfDisplay(to_standard_castle(board, mv))
+    } else {
+        IccfDisplay(mv)
+    }
+}
+
+struct IccfDisplay(Move);
+
+impl Display for IccfDisplay {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let Move { from, to, promotion } = self.0;
+        for square in [from, to] {
+            write!(f, "{}{}", square.file() as usize + 1, square.rank() as usize + 1)?;
+        }
+        if let Some(index) = promotion.and_then(|p| PROMOTIONS.iter().position(|&q| q == p)) {
+            write!(f, "{}", index + 1)?;
+        }
+        Ok(())
+    }
+}
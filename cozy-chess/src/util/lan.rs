@@ -0,0 +1,138 @@
+use core::convert::TryInto;
+use core::fmt::Display;
+
+use crate::*;
+
+use super::parse_san_move;
+
+/// Parses a Long Algebraic Notation move into a [`Move`].
+///
+/// LAN gives both the source and destination squares, separated by `-` for quiet moves and
+/// `x` for captures, like `Ng1-f3` and `e7xd8=Q`. The piece letter is omitted for pawns,
+/// the `=` before promotions is optional, and castling is written as `O-O` or `O-O-O`.
+/// Trailing check markers are ignored. The returned move is always legal.
+///
+/// # Examples
+///
+/// ```
+/// # use cozy_chess::*;
+/// # use cozy_chess::util::*;
+/// let board = Board::default();
+/// assert_eq!(parse_lan_move(&board, "Ng1-f3").unwrap(), "g1f3".parse().unwrap());
+/// assert_eq!(parse_lan_move(&board, "e2-e4").unwrap(), "e2e4".parse().unwrap());
+/// assert!(parse_lan_move(&board, "Bg1-f3").is_err());
+/// assert!(parse_lan_move(&board, "e2xe4").is_err());
+/// ```
+pub fn parse_lan_move(board: &impl Position, mv: &str) -> Result<Move, MoveParseError> {
+    let mv = mv.trim_end_matches(['+', '#']);
+    if mv.starts_with(['O', '0']) {
+        return parse_san_move(board, mv);
+    }
+
+    let mut chars = mv.chars().peekable();
+    let piece = match chars.next_if(char::is_ascii_uppercase) {
+        Some(c) => c.to_ascii_lowercase().try_into().map_err(|_| MoveParseError)?,
+        None => Piece::Pawn
+    };
+    fn square(chars: &mut impl Iterator<Item = char>) -> Result<Square, MoveParseError> {
+        let file = chars.next().ok_or(MoveParseError)?.try_into().map_err(|_| MoveParseError)?;
+        let rank = chars.next().ok_or(MoveParseError)?.try_into().map_err(|_| MoveParseError)?;
+        Ok(Square::new(file, rank))
+    }
+    let from = square(&mut chars)?;
+    let captures = match chars.next() {
+        Some('-') => false,
+        Some('x') => true,
+        _ => return Err(MoveParseError)
+    };
+    let to = square(&mut chars)?;
+    chars.next_if_eq(&'=');
+    let promotion = match chars.next() {
+        Some(c) if c.is_ascii_uppercase() => Some(c.to_ascii_lowercase().try_into().map_err(|_| MoveParseError)?),
+        Some(_) => return Err(MoveParseError),
+        None => None
+    };
+
+    let mv = Move { from, to, promotion };
+    if !board.is_legal(mv) || board.is_castle(mv) || board.moved_piece(mv) != Some(piece) || board.is_capture(mv) != captures {
+        return Err(MoveParseError);
+    }
+    Ok(mv)
+}
+
+/// Returns an object that allows printing a [`Move`] in Long Algebraic Notation.
+/// See [`parse_lan_move`] for the format.
+///
+/// # Errors
+/// This errors if the move is illegal.
+///
+/// # Examples
+///
+/// ```
+/// # use cozy_chess::*;
+/// # use cozy_chess::util::*;
+/// let board: Board = "3k2n1/7P/Q3p3/4BPp1/Q1Q4q/8/5B2/R3K2R w KQ g6 0 1"
+///     .parse().unwrap();
+/// let mv: Move = "h7g8r".parse().unwrap();
+/// assert_eq!(format!("{}", display_lan_move(&board, mv).unwrap()), "h7xg8=R+");
+/// let mv: Move = "e1a1".parse().unwrap();
+/// assert_eq!(format!("{}", display_lan_move(&board, mv).unwrap()), "O-O-O+");
+/// let mv: Move = "e5d4".parse().unwrap();
+/// assert_eq!(format!("{}", display_lan_move(&board, mv).unwrap()), "Be5-d4");
+/// ```
+pub fn display_lan_move(board: &impl Position, mv: Move) -> Result<impl Display, IllegalMoveError> {
+    let mut after_board = board.clone();
+    after_board.try_play(mv)?;
+
+    let check = !after_board.checkers().is_empty();
+    Ok(LanDisplay {
+        mv,
+        piece: board.moved_piece(mv).unwrap(),
+        captures: board.is_capture(mv),
+        castles: board.is_castle(mv),
+        check,
+        checkmate: check && !after_board.generate_moves(|_| true)
+    })
+}
+
+struct LanDisplay {
+    mv: Move,
+    piece: Piece,
+    captures: bool,
+    castles: bool,
+    check: bool,
+    checkmate: bool
+}
+
+impl Display for LanDisplay {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.castles {
+            if self.mv.from.file() < self.mv.to.file() {
+                write!(f, "O-O")?;
+            } else {
+                write!(f, "O-O-O")?;
+            }
+        } else {
+            if self.piece != Piece::Pawn {
+                write!(f, "{}", char::from(self.piece).to_ascii_uppercase())?;
+            }
+            let separator = if self.captures {
+                'x'
+            } else {
+                '-'
+            };
+            write!(f, "{}{}{}", self.mv.from, separator, self.mv.to)?;
+            if let Some(promotion) = self.mv.promotion {
+                write!(f, "={}", char::from(promotion).to_ascii_uppercase())?;
+            }
+        }
+
+        if self.checkmate {
+            write!(f, "#")?;
+        } else if self.check {
+            write!(f, "+")?;
+        }
+
+        Ok(())
+    }
+}
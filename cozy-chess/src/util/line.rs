@@ -0,0 +1,176 @@
+use core::fmt::{Display, Formatter};
+
+use crate::*;
+
+use super::display_san_move;
+#[cfg(feature = "std")]
+use super::{parse_san_move_with, parse_uci_move, SanOptions, SanParseError};
+
+/// An error for a move in a sequence of moves.
+/// # Examples
+/// ```
+/// # use cozy_chess::*;
+/// # use cozy_chess::util::*;
+/// let board = Board::default();
+/// let line = ["e2e4".parse().unwrap(), "e2e4".parse().unwrap()];
+/// let error = display_san_line(&board, &line).err().unwrap();
+/// assert_eq!(error.index, 1);
+/// assert_eq!(format!("{}", error), "Error at index 1: The move played was illegal.");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LineError<E> {
+    /// The 0-based index of the move in the sequence.
+    pub index: usize,
+    /// The error for the move.
+    pub error: E
+}
+
+impl<E: Display> Display for LineError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Error at index {}: {}", self.index, self.error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error> std::error::Error for LineError<E> {}
+
+/// Returns an object that allows printing a sequence of moves in SAN with move numbers,
+/// like `1. e4 e5 2. Nf3`. A sequence starting with black to move begins with a number
+/// followed by `...`, like `3... Nc6 4. Bb5`.
+///
+/// # Errors
+/// This errors with the index of the first illegal move in the sequence.
+///
+/// # Examples
+///
+/// ```
+/// # use cozy_chess::*;
+/// # use cozy_chess::util::*;
+/// let mut board = Board::default();
+/// let line = ["e2e4", "e7e5", "g1f3"].map(|mv| mv.parse().unwrap());
+/// let san = display_san_line(&board, &line).unwrap();
+/// assert_eq!(format!("{}", san), "1. e4 e5 2. Nf3");
+/// for &mv in &line {
+///     board.play(mv);
+/// }
+/// let line = ["b8c6", "f1b5"].map(|mv| mv.parse().unwrap());
+/// let san = display_san_line(&board, &line).unwrap();
+/// assert_eq!(format!("{}", san), "2... Nc6 3. Bb5");
+/// ```
+pub fn display_san_line<'l, P: Position + 'l>(board: &P, line: &'l [Move]) -> Result<impl Display + 'l, LineError<IllegalMoveError>> {
+    let mut after_board = board.clone();
+    for (index, &mv) in line.iter().enumerate() {
+        after_board.try_play(mv).map_err(|error| LineError { index, error })?;
+    }
+    Ok(SanLineDisplay {
+        board: board.clone(),
+        line
+    })
+}
+
+struct SanLineDisplay<'l, P> {
+    board: P,
+    line: &'l [Move]
+}
+
+impl<P: Position> Display for SanLineDisplay<'_, P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut board = self.board.clone();
+        for (index, &mv) in self.line.iter().enumerate() {
+            if index > 0 {
+                write!(f, " ")?;
+            }
+            match board.side_to_move() {
+                Color::White => write!(f, "{}. ", board.fullmove_number())?,
+                Color::Black if index == 0 => write!(f, "{}... ", board.fullmove_number())?,
+                Color::Black => {}
+            }
+            write!(f, "{}", display_san_move(&board, mv))?;
+            board.play_unchecked(mv);
+        }
+        Ok(())
+    }
+}
+
+/// Strip a move number like `12.` or `12...` from the start of a token.
+#[cfg(feature = "std")]
+fn strip_move_number(token: &str) -> &str {
+    let rest = token.trim_start_matches(|c: char| c.is_ascii_digit());
+    if rest.len() < token.len() && rest.starts_with('.') {
+        rest.trim_start_matches('.')
+    } else {
+        token
+    }
+}
+
+/// Parse a sequence of moves, returning the moves and the final board.
+#[cfg(feature = "std")]
+fn parse_line<'t, P: Position, E>(
+    board: &P,
+    tokens: impl Iterator<Item = &'t str>,
+    mut parse: impl FnMut(&P, &str) -> Result<Move, E>
+) -> Result<(Vec<Move>, P), LineError<E>> {
+    let mut board = board.clone();
+    let mut moves = Vec::new();
+    for token in tokens {
+        let mv = parse(&board, token).map_err(|error| LineError { index: moves.len(), error })?;
+        board.play_unchecked(mv);
+        moves.push(mv);
+    }
+    Ok((moves, board))
+}
+
+/// Parses a whitespace-separated sequence of SAN moves, returning the moves and the final board.
+/// Move numbers like `1.` and `1...` are skipped, so this accepts the output of [`display_san_line`].
+///
+/// # Errors
+/// This errors with the index of the first move that could not be parsed.
+///
+/// # Examples
+///
+/// ```
+/// # use cozy_chess::*;
+/// # use cozy_chess::util::*;
+/// let board = Board::default();
+/// let (line, after) = parse_san_line(&board, "1. e4 e5 2. Nf3").unwrap();
+/// assert_eq!(line, ["e2e4", "e7e5", "g1f3"].map(|mv| mv.parse().unwrap()));
+/// assert_eq!(format!("{}", after), "rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2");
+/// let error = parse_san_line(&board, "e4 e5 Ke3").unwrap_err();
+/// assert_eq!(error.index, 2);
+/// assert_eq!(error.error, SanParseError::Illegal);
+/// ```
+#[cfg(feature = "std")]
+pub fn parse_san_line<P: Position>(board: &P, line: &str) -> Result<(Vec<Move>, P), LineError<SanParseError>> {
+    let tokens = line.split_whitespace()
+        .map(strip_move_number)
+        .filter(|token| !token.is_empty());
+    parse_line(board, tokens, |board, mv| parse_san_move_with(board, mv, &SanOptions::default()))
+}
+
+/// Parses a whitespace-separated sequence of UCI moves, returning the moves and the final board.
+///
+/// # Errors
+/// This errors with the index of the first move that could not be parsed or was illegal.
+///
+/// # Examples
+///
+/// ```
+/// # use cozy_chess::*;
+/// # use cozy_chess::util::*;
+/// let board = Board::default();
+/// let (line, after) = parse_uci_line(&board, "e2e4 e7e5 g1f3").unwrap();
+/// assert_eq!(line.len(), 3);
+/// assert_eq!(after.fullmove_number(), 2);
+/// assert_eq!(parse_uci_line(&board, "e2e4 e2e4").unwrap_err().index, 1);
+/// ```
+#[cfg(feature = "std")]
+pub fn parse_uci_line<P: Position>(board: &P, line: &str) -> Result<(Vec<Move>, P), LineError<MoveParseError>> {
+    parse_line(board, line.split_whitespace(), |board, mv| {
+        let mv = parse_uci_move(board, mv)?;
+        if board.is_legal(mv) {
+            Ok(mv)
+        } else {
+            Err(MoveParseError)
+        }
+    })
+}
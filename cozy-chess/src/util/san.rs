@@ -0,0 +1,494 @@
+use core::convert::TryInto;
+use core::fmt::Display;
+
+use crate::*;
+
+/// English piece letters, indexed by [`Piece`].
+pub const ENGLISH_PIECE_LETTERS: [char; Piece::NUM] = ['P', 'N', 'B', 'R', 'Q', 'K'];
+
+/// German piece letters, indexed by [`Piece`].
+pub const GERMAN_PIECE_LETTERS: [char; Piece::NUM] = ['B', 'S', 'L', 'T', 'D', 'K'];
+
+/// French piece letters, indexed by [`Piece`].
+pub const FRENCH_PIECE_LETTERS: [char; Piece::NUM] = ['P', 'C', 'F', 'T', 'D', 'R'];
+
+/// Options for displaying and parsing Standard Algebraic Notation.
+/// See [`display_san_move_with`] and [`parse_san_move_with`].
+/// # Examples
+/// ```
+/// # use cozy_chess::*;
+/// # use cozy_chess::util::*;
+/// let options = SanOptions {
+///     piece_letters: GERMAN_PIECE_LETTERS,
+///     zero_castling: true,
+///     ..SanOptions::STANDARD
+/// };
+/// let mut board = Board::default();
+/// let mv = parse_san_move_with(&board, "Sf3", &options).unwrap();
+/// assert_eq!(mv, "g1f3".parse().unwrap());
+/// let figurines = SanOptions {
+///     figurines: true,
+///     ..SanOptions::STANDARD
+/// };
+/// assert_eq!(format!("{}", display_san_move_with(&board, mv, &figurines).unwrap()), "♘f3");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SanOptions {
+    /// The uppercase letter for each piece, indexed by [`Piece`].
+    /// The pawn letter is never displayed, but is accepted before pawn moves when parsing.
+    pub piece_letters: [char; Piece::NUM],
+    /// Display pieces as figurines such as `♘` instead of letters.
+    /// Figurines of either color are always accepted when parsing.
+    pub figurines: bool,
+    /// Display castling with zeros (`0-0`) instead of the letter O (`O-O`).
+    /// Both are always accepted when parsing.
+    pub zero_castling: bool,
+    /// Display an `=` before the promotion piece. It is always optional when parsing.
+    pub promotion_equals: bool,
+    /// Display ` e.p.` after en passant captures, following any check marker. It is always optional when parsing.
+    pub en_passant_suffix: bool,
+    /// Parse leniently, accepting the non-canonical SAN found in real-world PGNs. This accepts
+    /// annotation glyphs such as `!?`, lowercase piece letters (trying a lowercase `b` as both a
+    /// pawn file and a bishop), `:` for captures, `-` between the source and destination squares,
+    /// and `o-o` castling. This has no effect on display.
+    pub lenient: bool
+}
+
+impl SanOptions {
+    /// Canonical SAN with English piece letters, as used by PGN.
+    pub const STANDARD: Self = Self {
+        piece_letters: ENGLISH_PIECE_LETTERS,
+        figurines: false,
+        zero_castling: false,
+        promotion_equals: true,
+        en_passant_suffix: false,
+        lenient: false
+    };
+
+    /// [`SanOptions::STANDARD`] with lenient parsing.
+    pub const LENIENT: Self = Self {
+        lenient: true,
+        ..Self::STANDARD
+    };
+
+    fn parse_piece(&self, c: char) -> Option<Piece> {
+        let c = if self.lenient {
+            c.to_ascii_uppercase()
+        } else {
+            c
+        };
+        let index = self.piece_letters.iter().position(|&l| l == c)
+            .or_else(|| WHITE_FIGURINES.iter().position(|&l| l == c))
+            .or_else(|| BLACK_FIGURINES.iter().position(|&l| l == c))?;
+        Some(Piece::index(index))
+    }
+
+    fn piece_char(&self, piece: Piece) -> char {
+        if self.figurines {
+            WHITE_FIGURINES[piece as usize]
+        } else {
+            self.piece_letters[piece as usize]
+        }
+    }
+}
+
+impl Default for SanOptions {
+    fn default() -> Self {
+        Self::STANDARD
+    }
+}
+
+helpers::simple_error! {
+    /// An error while parsing a SAN move. See [`parse_san_move_with`].
+    #[derive(PartialEq, Eq, Hash)]
+    pub enum SanParseError {
+        Malformed = "The value is not a valid SAN move.",
+        Illegal = "No legal move matches the SAN move.",
+        Ambiguous = "More than one legal move matches the SAN move."
+    }
+}
+
+/// Parses a Standard Algebraic Notation move into a [`Move`].
+///
+/// Canonical SAN is guaranteed to parse correctly, but non-canonical SAN may or may not parse.
+/// The returned move is always legal. This accepts every style [`SanOptions::STANDARD`] accepts.
+/// See also: [`parse_san_move_with`].
+///
+/// # Examples
+///
+/// ```
+/// # use cozy_chess::*;
+/// # use cozy_chess::util::*;
+/// let board: Board = "3k2n1/7P/Q3p3/4BPp1/Q1Q4q/8/5B2/R3K2R w KQ g6 0 1"
+///     .parse().unwrap();
+/// let mv: Move = "h7g8r".parse().unwrap();
+/// assert_eq!(parse_san_move(&board, "hxg8=R").unwrap(), mv);
+/// let mv: Move = "e1a1".parse().unwrap();
+/// assert_eq!(parse_san_move(&board, "O-O-O+").unwrap(), mv);
+/// let mv: Move = "e5d4".parse().unwrap();
+/// assert_eq!(parse_san_move(&board, "Bd4").unwrap(), mv);
+/// ```
+pub fn parse_san_move(board: &impl Position, mv: &str) -> Result<Move, MoveParseError> {
+    parse_san_move_with(board, mv, &SanOptions::STANDARD).map_err(|_| MoveParseError)
+}
+
+/// Parses a Standard Algebraic Notation move into a [`Move`] with some [`SanOptions`].
+///
+/// Pieces may be given as the letters in [`SanOptions::piece_letters`] or as figurines. Castling may
+/// be written with `O` or `0`, and the `=` before promotions and the `e.p.` after en passant
+/// captures are optional. See [`SanOptions::lenient`] for the non-canonical input accepted
+/// in lenient mode. The returned move is always legal.
+///
+/// # Errors
+/// Returns [`SanParseError::Malformed`] if the value is not SAN, [`SanParseError::Illegal`] if
+/// no legal move matches it, and [`SanParseError::Ambiguous`] if more than one does.
+///
+/// # Examples
+///
+/// ```
+/// # use cozy_chess::*;
+/// # use cozy_chess::util::*;
+/// let board: Board = "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3"
+///     .parse().unwrap();
+/// let options = SanOptions::STANDARD;
+/// let mv: Move = "e5f6".parse().unwrap();
+/// assert_eq!(parse_san_move_with(&board, "exf6 e.p.", &options), Ok(mv));
+/// assert_eq!(parse_san_move_with(&board, "exf6", &options), Ok(mv));
+/// let mv: Move = "g1f3".parse().unwrap();
+/// assert_eq!(parse_san_move_with(&board, "♘f3", &options), Ok(mv));
+/// assert_eq!(parse_san_move_with(&board, "Nf3!?", &options), Err(SanParseError::Malformed));
+/// assert_eq!(parse_san_move_with(&board, "Nf3!?", &SanOptions::LENIENT), Ok(mv));
+/// assert_eq!(parse_san_move_with(&board, "Nf4", &options), Err(SanParseError::Illegal));
+/// let board: Board = "4k3/8/8/8/8/8/4K3/R6R w - - 0 1".parse().unwrap();
+/// assert_eq!(parse_san_move_with(&board, "Rd1", &options), Err(SanParseError::Ambiguous));
+/// ```
+pub fn parse_san_move_with(board: &impl Position, mv: &str, options: &SanOptions) -> Result<Move, SanParseError> {
+    let mut mv = mv;
+    let suffixes: &[char] = if options.lenient {
+        mv = mv.trim();
+        &['+', '#', '!', '?']
+    } else {
+        &['+', '#']
+    };
+    // The check marker may come before or after the en passant suffix
+    mv = mv.trim_end_matches(suffixes);
+    mv = mv.strip_suffix("e.p.").map_or(mv, |mv| mv.trim_end().trim_end_matches(suffixes));
+
+    let result = parse_san_body(board, mv, options, Piece::Pawn);
+    if options.lenient && mv.starts_with('b') && options.piece_letters[Piece::Bishop as usize] == 'B' {
+        // A lowercase b could be a pawn file or a bishop.
+        let bishop_result = parse_san_body(board, &mv[1..], options, Piece::Bishop);
+        return match (result, bishop_result) {
+            (Ok(_), Ok(_)) => Err(SanParseError::Ambiguous),
+            (Ok(mv), _) | (_, Ok(mv)) => Ok(mv),
+            (Err(SanParseError::Ambiguous), _) | (_, Err(SanParseError::Ambiguous)) => Err(SanParseError::Ambiguous),
+            (Err(SanParseError::Illegal), _) | (_, Err(SanParseError::Illegal)) => Err(SanParseError::Illegal),
+            _ => Err(SanParseError::Malformed)
+        };
+    }
+    result
+}
+
+/// Parse the SAN without annotations. `default_piece` is the piece moved if no piece is given.
+fn parse_san_body(
+    board: &impl Position,
+    mv: &str,
+    options: &SanOptions,
+    default_piece: Piece
+) -> Result<Move, SanParseError> {
+    use SanParseError::*;
+
+    // SAN is easier to parse backwards
+    let mut chars = mv.chars().rev().peekable();
+
+    let dst;
+    let src_rank: Option<Rank>;
+    let src_file: Option<File>;
+    let piece;
+    let promotion;
+
+    let is_castle = |c: char| c == 'O' || c == '0' || (options.lenient && c == 'o');
+    if let Some(castle) = chars.next_if(|&c| is_castle(c)) {
+        // Castles
+
+        chars.next_if_eq(&'-').ok_or(Malformed)?;
+        chars.next_if_eq(&castle).ok_or(Malformed)?;
+
+        let rook_file = if chars.next_if_eq(&'-').is_some() {
+            chars.next_if_eq(&castle).ok_or(Malformed)?;
+            board.castle_rights(board.side_to_move()).long
+        } else {
+            board.castle_rights(board.side_to_move()).short
+        };
+
+        if chars.next().is_some() {
+            return Err(Malformed);
+        }
+        dst = Square::new(
+            rook_file.ok_or(Illegal)?,
+            board.king(board.side_to_move()).rank(),
+        );
+        piece = Piece::King;
+        src_file = None;
+        src_rank = None;
+        promotion = None;
+    } else {
+        // Non-castles
+
+        promotion = chars.peek().and_then(|&c| options.parse_piece(c));
+
+        if promotion.is_some() {
+            // Consume promotion character
+            chars.next();
+            // Consume optional '='
+            chars.next_if_eq(&'=');
+        }
+        // Destination square
+        let dst_rank = chars
+            .next()
+            .and_then(|c| c.try_into().ok())
+            .ok_or(Malformed)?;
+        let dst_file = chars
+            .next()
+            .and_then(|c| c.try_into().ok())
+            .ok_or(Malformed)?;
+        dst = Square::new(dst_file, dst_rank);
+
+        // Consume optional captures
+        if chars.next_if_eq(&'x').is_none() && options.lenient {
+            chars.next_if(|&c| c == ':' || c == '-');
+        }
+
+        // Source square
+        src_rank = chars.peek().and_then(|&c| c.try_into().ok());
+        if src_rank.is_some() {
+            chars.next();
+        }
+        src_file = chars.peek().and_then(|&c| c.try_into().ok());
+        if src_file.is_some() {
+            chars.next();
+        }
+
+        // Piece
+        piece = chars.next().map_or(Ok(default_piece), |c| {
+            options.parse_piece(c).ok_or(Malformed)
+        })?;
+
+        if chars.next().is_some() {
+            // too many characters
+            return Err(Malformed);
+        }
+    }
+
+    let mut src_mask = board.colored_pieces(board.side_to_move(), piece);
+    if let Some(src_rank) = src_rank {
+        src_mask &= src_rank.bitboard();
+    }
+    if let Some(src_file) = src_file {
+        src_mask &= src_file.bitboard();
+    }
+
+    let mut mv = Err(Illegal);
+    board.generate_moves_for(src_mask, |mut mvs| {
+        mvs.to &= dst.bitboard();
+        for m in mvs {
+            if m.promotion != promotion {
+                continue;
+            }
+            if mv.is_ok() {
+                mv = Err(Ambiguous);
+                return true;
+            }
+            mv = Ok(m);
+        }
+        false
+    });
+    mv
+}
+
+/// Returns an object that allows printing a [`Move`] in Standard Algebraic Notation.
+///
+/// # Panics
+/// This is guaranteed to panic if the move is illegal.
+/// See [`display_san_move_with`] for a fallible version.
+///
+/// # Examples
+///
+/// ```
+/// # use cozy_chess::*;
+/// # use cozy_chess::util::*;
+/// let board: Board = "3k2n1/7P/Q3p3/4BPp1/Q1Q4q/8/5B2/R3K2R w KQ g6 0 1"
+///     .parse().unwrap();
+/// let mv: Move = "h7g8r".parse().unwrap();
+/// assert_eq!(format!("{}", display_san_move(&board, mv)), "hxg8=R+");
+/// let mv: Move = "e1a1".parse().unwrap();
+/// assert_eq!(format!("{}", display_san_move(&board, mv)), "O-O-O+");
+/// let mv: Move = "e5d4".parse().unwrap();
+/// assert_eq!(format!("{}", display_san_move(&board, mv)), "Bd4");
+/// ```
+pub fn display_san_move(board: &impl Position, mv: Move) -> impl Display {
+    display_san_move_with(board, mv, &SanOptions::STANDARD).unwrap()
+}
+
+/// Returns an object that allows printing a [`Move`] in Standard Algebraic Notation with some [`SanOptions`].
+///
+/// # Errors
+/// This errors if the move is illegal.
+///
+/// # Examples
+///
+/// ```
+/// # use cozy_chess::*;
+/// # use cozy_chess::util::*;
+/// let board: Board = "3k2n1/7P/Q3p3/4BPp1/Q1Q4q/8/5B2/R3K2R w KQ g6 0 1"
+///     .parse().unwrap();
+/// let options = SanOptions {
+///     piece_letters: GERMAN_PIECE_LETTERS,
+///     zero_castling: true,
+///     promotion_equals: false,
+///     en_passant_suffix: true,
+///     ..SanOptions::STANDARD
+/// };
+/// let mv: Move = "h7g8r".parse().unwrap();
+/// assert_eq!(format!("{}", display_san_move_with(&board, mv, &options).unwrap()), "hxg8T+");
+/// let mv: Move = "e1a1".parse().unwrap();
+/// assert_eq!(format!("{}", display_san_move_with(&board, mv, &options).unwrap()), "0-0-0+");
+/// let mv: Move = "f5g6".parse().unwrap();
+/// assert_eq!(format!("{}", display_san_move_with(&board, mv, &options).unwrap()), "fxg6 e.p.");
+/// let mv: Move = "e1e3".parse().unwrap();
+/// assert!(display_san_move_with(&board, mv, &options).is_err());
+/// ```
+pub fn display_san_move_with(board: &impl Position, mv: Move, options: &SanOptions) -> Result<impl Display, IllegalMoveError> {
+    let mut after_board = board.clone();
+    after_board.try_play(mv)?;
+
+    let check = !after_board.checkers().is_empty();
+    let checkmate = check && !after_board.generate_moves(|_| true);
+
+    let piece = board.moved_piece(mv).unwrap();
+    let captures = board.is_capture(mv);
+    let en_passant = board.is_en_passant(mv);
+
+    if board.is_castle(mv) {
+        let short = mv.from.file() < mv.to.file();
+        return Ok(SanDisplay {
+            options: *options,
+            piece: None,
+            from_file: None,
+            from_rank: None,
+            captures,
+            to_sq: mv.to,
+            promotion: None,
+            en_passant,
+            check,
+            checkmate,
+            long_castles: !short,
+            short_castles: short,
+        });
+    }
+
+    let mut file_disambiguates = true;
+    let mut rank_disambiguates = true;
+    let mut ambiguous = false;
+
+    board.generate_moves_for(board.colored_pieces(board.side_to_move(), piece), |mvs| {
+        if mvs.from != mv.from && mvs.to.has(mv.to) {
+            ambiguous = true;
+            if mvs.from.file() == mv.from.file() {
+                file_disambiguates = false;
+            }
+            if mvs.from.rank() == mv.from.rank() {
+                rank_disambiguates = false;
+            }
+        }
+        false
+    });
+
+    if piece == Piece::Pawn && captures {
+        ambiguous = true;
+    }
+
+    let (from_file, from_rank) = match (ambiguous, file_disambiguates, rank_disambiguates) {
+        (false, _, _) => (None, None),
+        (true, true, _) => (Some(mv.from.file()), None),
+        (true, false, false) => (Some(mv.from.file()), Some(mv.from.rank())),
+        (true, false, true) => (None, Some(mv.from.rank())),
+    };
+
+    Ok(SanDisplay {
+        options: *options,
+        piece: (piece != Piece::Pawn).then_some(piece),
+        from_file,
+        from_rank,
+        captures,
+        to_sq: mv.to,
+        promotion: mv.promotion,
+        en_passant,
+        check,
+        checkmate,
+        long_castles: false,
+        short_castles: false,
+    })
+}
+
+struct SanDisplay {
+    options: SanOptions,
+    piece: Option<Piece>,
+    from_file: Option<File>,
+    from_rank: Option<Rank>,
+    captures: bool,
+    to_sq: Square,
+    promotion: Option<Piece>,
+    en_passant: bool,
+    check: bool,
+    checkmate: bool,
+    long_castles: bool,
+    short_castles: bool,
+}
+
+impl Display for SanDisplay {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let castle = if self.options.zero_castling {
+            '0'
+        } else {
+            'O'
+        };
+        if self.long_castles {
+            write!(f, "{0}-{0}-{0}", castle)?;
+        } else if self.short_castles {
+            write!(f, "{0}-{0}", castle)?;
+        } else {
+            if let Some(piece) = self.piece {
+                write!(f, "{}", self.options.piece_char(piece))?;
+            }
+            if let Some(file) = self.from_file {
+                write!(f, "{file}")?;
+            }
+            if let Some(rank) = self.from_rank {
+                write!(f, "{rank}")?;
+            }
+            if self.captures {
+                write!(f, "x")?;
+            }
+            write!(f, "{}", self.to_sq)?;
+            if let Some(promo) = self.promotion {
+                if self.options.promotion_equals {
+                    write!(f, "=")?;
+                }
+                write!(f, "{}", self.options.piece_char(promo))?;
+            }
+        }
+
+        if self.checkmate {
+            write!(f, "#")?;
+        } else if self.check {
+            write!(f, "+")?;
+        }
+        // The check marker belongs to the move, so it comes before the suffix.
+        if self.en_passant && self.options.en_passant_suffix {
+            write!(f, " e.p.")?;
+        }
+
+        Ok(())
+    }
+}
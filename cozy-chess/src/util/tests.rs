@@ -43,6 +43,206 @@ fn handles_canonical_san() {
     for (mv, san) in moves {
         let mv = mv.parse().unwrap();
         assert_eq!(san, format!("{}", display_san_move(&board, mv)));
-        assert_eq!(mv, parse_san_move(&board, san).expect(&san));
+        assert_eq!(mv, parse_san_move(&board, san).expect(san));
     }
 }
+
+#[test]
+fn san_options_round_trip() {
+    let mut all_options = Vec::new();
+    for &piece_letters in &[ENGLISH_PIECE_LETTERS, GERMAN_PIECE_LETTERS, FRENCH_PIECE_LETTERS] {
+        for flags in 0..32 {
+            all_options.push(SanOptions {
+                piece_letters,
+                figurines: flags & 1 != 0,
+                zero_castling: flags & 2 != 0,
+                promotion_equals: flags & 4 != 0,
+                en_passant_suffix: flags & 8 != 0,
+                lenient: flags & 16 != 0
+            });
+        }
+    }
+    let fens = [
+        "3k2n1/7P/Q3p3/4BPp1/Q1Q4q/8/5B2/R3K2R w KQ g6 0 1",
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1"
+    ];
+    for fen in fens {
+        let board: Board = fen.parse().unwrap();
+        board.generate_moves(|mvs| {
+            for mv in mvs {
+                for options in &all_options {
+                    let san = format!("{}", display_san_move_with(&board, mv, options).unwrap());
+                    assert_eq!(parse_san_move_with(&board, &san, options).expect(&san), mv, "{}", san);
+                }
+            }
+            false
+        });
+    }
+}
+
+#[test]
+fn san_styles() {
+    let board: Board = "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3".parse().unwrap();
+    let ep: Move = "e5f6".parse().unwrap();
+    for san in ["exf6", "exf6 e.p.", "exf6e.p.", "ef6"] {
+        assert_eq!(parse_san_move(&board, san).ok(), Some(ep), "{}", san);
+    }
+    let options = SanOptions {
+        en_passant_suffix: true,
+        ..SanOptions::STANDARD
+    };
+    assert_eq!(format!("{}", display_san_move_with(&board, ep, &options).unwrap()), "exf6 e.p.");
+    assert_eq!(format!("{}", display_san_move(&board, ep)), "exf6");
+
+    let board: Board = "8/2k5/8/3pP3/8/8/8/4K3 w - d6 0 1".parse().unwrap();
+    let ep: Move = "e5d6".parse().unwrap();
+    let san = format!("{}", display_san_move_with(&board, ep, &options).unwrap());
+    assert_eq!(san, "exd6+ e.p.");
+    assert_eq!(parse_san_move(&board, &san).ok(), Some(ep));
+
+    let board: Board = "3k2n1/7P/Q3p3/4BPp1/Q1Q4q/8/5B2/R3K2R w KQ g6 0 1".parse().unwrap();
+    let castle: Move = "e1a1".parse().unwrap();
+    for san in ["O-O-O+", "0-0-0+", "0-0-0"] {
+        assert_eq!(parse_san_move(&board, san).ok(), Some(castle), "{}", san);
+    }
+    assert!(parse_san_move(&board, "0-O-0").is_err());
+    let promotion: Move = "h7g8n".parse().unwrap();
+    for san in ["hxg8=N", "hxg8N", "hxg8♘", "hxg8=♞"] {
+        assert_eq!(parse_san_move(&board, san).ok(), Some(promotion), "{}", san);
+    }
+    let german = SanOptions {
+        piece_letters: GERMAN_PIECE_LETTERS,
+        ..SanOptions::STANDARD
+    };
+    assert_eq!(parse_san_move_with(&board, "hxg8=S", &german).ok(), Some(promotion));
+    assert_eq!(parse_san_move_with(&board, "Lh2", &german).ok(), Some("e5h2".parse().unwrap()));
+    assert!(parse_san_move_with(&board, "Bh2", &german).is_err());
+
+    let illegal: Move = "e1e3".parse().unwrap();
+    assert!(display_san_move_with(&board, illegal, &SanOptions::STANDARD).is_err());
+}
+
+#[test]
+fn lenient_san() {
+    let board: Board = "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3".parse().unwrap();
+    let cases = [
+        ("Bb5!?", "f1b5"), ("bb5", "f1b5"), ("Bc4!!", "f1c4"), ("bc4", "f1c4"),
+        ("Ng1f3", ""), ("nxe5", "f3e5"), ("N:e5", "f3e5"), ("Nf3xe5?", "f3e5"),
+        ("Nf3-e5", "f3e5"), ("b3", "b2b3"), ("b4?!", "b2b4"), ("e4-e5", ""),
+        (" Ke2 ", "e1e2"), ("qe2", "d1e2"), ("d2-d4!", "d2d4"), ("o-o", "")
+    ];
+    for (san, expected) in cases {
+        let result = parse_san_move_with(&board, san, &SanOptions::LENIENT);
+        match expected {
+            "" => assert!(result.is_err(), "{}", san),
+            expected => assert_eq!(result, Ok(expected.parse().unwrap()), "{}", san)
+        }
+    }
+
+    let board: Board = "4k3/8/8/8/8/1P1P4/2P5/1B2K3 w - - 0 1".parse().unwrap();
+    assert_eq!(parse_san_move_with(&board, "bxc2", &SanOptions::LENIENT), Err(SanParseError::Illegal));
+    let board: Board = "4k3/8/8/8/8/2p5/1P6/4KB2 w - - 0 1".parse().unwrap();
+    assert_eq!(parse_san_move_with(&board, "bxc3", &SanOptions::LENIENT), Ok("b2c3".parse().unwrap()));
+    let board: Board = "4k3/8/8/8/8/2p5/1P6/4K3 w - - 0 1".parse().unwrap();
+    assert_eq!(parse_san_move_with(&board, "bxc3", &SanOptions::LENIENT), Ok("b2c3".parse().unwrap()));
+    let board: Board = "4k3/8/8/4B3/8/2p5/1P6/4K3 w - - 0 1".parse().unwrap();
+    assert_eq!(parse_san_move_with(&board, "bxc3", &SanOptions::LENIENT), Err(SanParseError::Ambiguous));
+    assert_eq!(parse_san_move_with(&board, "Bxc3", &SanOptions::LENIENT), Ok("e5c3".parse().unwrap()));
+
+    let board: Board = "r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1".parse().unwrap();
+    assert_eq!(parse_san_move_with(&board, "o-o-o", &SanOptions::LENIENT), Ok("e8a8".parse().unwrap()));
+    assert_eq!(parse_san_move_with(&board, "o-o", &SanOptions::STANDARD), Err(SanParseError::Malformed));
+    assert_eq!(parse_san_move_with(&board, "O-O-O-O", &SanOptions::STANDARD), Err(SanParseError::Malformed));
+    assert_eq!(parse_san_move_with(&board, "Rxa1", &SanOptions::STANDARD), Ok("a8a1".parse().unwrap()));
+    assert_eq!(parse_san_move_with(&board, "Rb8", &SanOptions::STANDARD), Ok("a8b8".parse().unwrap()));
+    assert_eq!(parse_san_move_with(&board, "Rf8", &SanOptions::STANDARD), Ok("h8f8".parse().unwrap()));
+    assert_eq!(parse_san_move_with(&board, "Rd8", &SanOptions::STANDARD), Ok("a8d8".parse().unwrap()));
+    assert_eq!(parse_san_move_with(&board, "Qd8", &SanOptions::STANDARD), Err(SanParseError::Illegal));
+    assert_eq!(parse_san_move_with(&board, "Zd8", &SanOptions::STANDARD), Err(SanParseError::Malformed));
+}
+
+#[test]
+fn notation_round_trips() {
+    let fens = [
+        "3k2n1/7P/Q3p3/4BPp1/Q1Q4q/8/5B2/R3K2R w KQ g6 0 1",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        "2k5/1P6/8/8/8/8/6p1/4K2N b - - 0 1",
+        "rk4r1/8/8/8/8/8/8/RK4R1 w GAga - 0 1",
+        "rk4r1/8/8/8/8/8/8/RK4R1 b GAga - 0 1",
+        "3rk1r1/8/8/8/8/8/8/3RK1R1 w GDgd - 0 1",
+        "rkb2bnr/pp2pppp/2p1n3/3p4/q2P4/5NP1/PPP1PP1P/RKBNQBR1 w Aha - 0 9"
+    ];
+    for fen in fens {
+        let board: Board = fen.parse().unwrap();
+        board.generate_moves(|mvs| {
+            for mv in mvs {
+                let lan = format!("{}", display_lan_move(&board, mv).unwrap());
+                assert_eq!(parse_lan_move(&board, &lan).ok(), Some(mv), "{}", lan);
+                let iccf = format!("{}", display_iccf_move(&board, mv));
+                assert_eq!(parse_iccf_move(&board, &iccf).ok(), Some(mv), "{}", iccf);
+                let descriptive = format!("{}", display_descriptive_move(&board, mv).unwrap());
+                assert_eq!(parse_descriptive_move(&board, &descriptive).ok(), Some(mv), "{}", descriptive);
+            }
+            false
+        });
+    }
+}
+
+#[test]
+fn descriptive_moves() {
+    let board: Board = "r1bqkbnr/pppp1ppp/2n5/4p3/3PP3/5N2/PPP2PPP/RNBQKB1R b KQkq - 0 3".parse().unwrap();
+    let cases = [
+        ("PxP", "e5d4"),
+        ("NxP", "c6d4"),
+        ("P-Q3", "d7d6"),
+        ("N-B3", "g8f6"),
+        ("Kt-KB3", "g8f6"),
+        ("N/N1-B3", "g8f6"),
+        ("B-N5ch", "f8b4"),
+        ("B-QN5+", "f8b4"),
+        ("Q-K2", "d8e7"),
+        ("Q-R5!?", "d8h4"),
+        ("P-R3", ""),
+        ("P-KR3", "h7h6"),
+        ("B-B4", "f8c5"),
+        ("N-R3", "g8h6"),
+        ("PxQ", ""),
+        ("O-O", ""),
+        ("N-K4", "")
+    ];
+    for (descriptive, expected) in cases {
+        let result = parse_descriptive_move(&board, descriptive);
+        match expected {
+            "" => assert!(result.is_err(), "{}", descriptive),
+            expected => assert_eq!(result.ok(), Some(expected.parse().unwrap()), "{}", descriptive)
+        }
+    }
+
+    let board: Board = "4k3/1P6/8/3pP3/8/8/8/4K3 w - d6 0 1".parse().unwrap();
+    assert_eq!(parse_descriptive_move(&board, "PxP e.p.").ok(), Some("e5d6".parse().unwrap()));
+    assert_eq!(parse_descriptive_move(&board, "P-N8=Q").ok(), Some("b7b8q".parse().unwrap()));
+    assert_eq!(parse_descriptive_move(&board, "P-N8(N)").ok(), Some("b7b8n".parse().unwrap()));
+    let mv = "b7b8q".parse().unwrap();
+    assert_eq!(format!("{}", display_descriptive_move(&board, mv).unwrap()), "P-N8=Qch");
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn san_line_round_trip() {
+    let board: Board = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R b KQkq - 0 7".parse().unwrap();
+    let uci = "e8c8 e1g1 b4c3 d2c3 g7f8 d5d6";
+    let (line, after) = parse_uci_line(&board, uci).unwrap();
+    let san = format!("{}", display_san_line(&board, &line).unwrap());
+    assert_eq!(san, "7... O-O-O 8. O-O bxc3 9. Bxc3 Bf8 10. d6");
+    let (san_line, san_after) = parse_san_line(&board, &san).unwrap();
+    assert_eq!(san_line, line);
+    assert_eq!(san_after, after);
+    assert_eq!(parse_san_line(&board, "7...O-O-O 8.O-O").unwrap().0, line[..2]);
+    assert_eq!(display_san_line(&board, &[]).unwrap().to_string(), "");
+    assert_eq!(parse_san_line(&board, "O-O-O O-O Bxc3").unwrap_err(), LineError {
+        index: 2,
+        error: SanParseError::Illegal
+    });
+}
@@ -587,6 +587,17 @@ impl FromIterator<Square> for BitBoard {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for BitBoard {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self(u.arbitrary()?))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <u64 as arbitrary::Arbitrary>::size_hint(depth)
+    }
+}
+
 /// An iterator over the squares of a bitboard.
 /// 
 /// This `struct` is created by [`BitBoard::iter`]. See its documentation for more.
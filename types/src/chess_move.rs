@@ -51,3 +51,21 @@ impl core::fmt::Display for Move {
         Ok(())
     }
 }
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Move {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        const PROMOTIONS: [Option<Piece>; 5] = [
+            None,
+            Some(Piece::Knight),
+            Some(Piece::Bishop),
+            Some(Piece::Rook),
+            Some(Piece::Queen)
+        ];
+        Ok(Move {
+            from: u.arbitrary()?,
+            to: u.arbitrary()?,
+            promotion: *u.choose(&PROMOTIONS)?
+        })
+    }
+}
@@ -63,6 +63,13 @@ macro_rules! simple_enum {
                 }
             }
         }
+
+        #[cfg(feature = "arbitrary")]
+        impl<'a> arbitrary::Arbitrary<'a> for $name {
+            fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+                Ok(Self::index(u.choose_index(Self::NUM)?))
+            }
+        }
     };
 }
 pub(crate) use simple_enum;
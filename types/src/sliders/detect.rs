@@ -0,0 +1,76 @@
+use core::sync::atomic::{AtomicU8, Ordering};
+
+const UNKNOWN: u8 = 0;
+const SLOW: u8 = 1;
+const FAST: u8 = 2;
+
+static PEXT_STATUS: AtomicU8 = AtomicU8::new(UNKNOWN);
+
+/// Check if the CPU supports BMI2 and implements PEXT in hardware.
+/// AMD CPUs before Zen 3 support BMI2 but implement PEXT in microcode,
+/// which is much slower than magic bitboards.
+#[cfg(not(target_env = "sgx"))]
+// CPUID intrinsics are unsafe on older compilers.
+#[allow(unused_unsafe)]
+fn detect() -> bool {
+    use core::arch::x86_64::{__cpuid, __cpuid_count};
+
+    // SAFETY: CPUID is always available on x86_64 outside of SGX.
+    let (vendor, max_leaf) = unsafe {
+        let leaf = __cpuid(0);
+        let mut vendor = [0; 12];
+        vendor[0..4].copy_from_slice(&leaf.ebx.to_le_bytes());
+        vendor[4..8].copy_from_slice(&leaf.edx.to_le_bytes());
+        vendor[8..12].copy_from_slice(&leaf.ecx.to_le_bytes());
+        (vendor, leaf.eax)
+    };
+    if max_leaf < 7 {
+        return false;
+    }
+    // SAFETY: Leaves 1 and 7 are supported, as checked above.
+    let (signature, features) = unsafe { (__cpuid(1).eax, __cpuid_count(7, 0).ebx) };
+    let bmi2 = features & (1 << 8) != 0;
+    if !bmi2 {
+        return false;
+    }
+
+    let base_family = (signature >> 8) & 0xF;
+    let family = if base_family == 0xF {
+        base_family + ((signature >> 20) & 0xFF)
+    } else {
+        base_family
+    };
+    let microcoded = match &vendor {
+        // Zen 1 and Zen 2 are family 17h. Zen 3 is family 19h.
+        b"AuthenticAMD" => family < 0x19,
+        // Hygon Dhyana is based on Zen 1.
+        b"HygonGenuine" => true,
+        _ => false
+    };
+    !microcoded
+}
+
+#[cfg(target_env = "sgx")]
+fn detect() -> bool {
+    false
+}
+
+/// Check if PEXT should be used for slider lookups on this CPU.
+/// The result is detected once and cached.
+#[inline(always)]
+pub fn pext_is_fast() -> bool {
+    match PEXT_STATUS.load(Ordering::Relaxed) {
+        FAST => true,
+        SLOW => false,
+        _ => detect_and_cache()
+    }
+}
+
+// Kept out of line so the cached check stays small enough to inline into every lookup.
+#[cold]
+#[inline(never)]
+fn detect_and_cache() -> bool {
+    let fast = detect();
+    PEXT_STATUS.store(if fast { FAST } else { SLOW }, Ordering::Relaxed);
+    fast
+}
@@ -0,0 +1,28 @@
+use crate::*;
+
+use super::{magic, pext, pext_is_fast};
+
+/// The offset of the PEXT moves in the slider move table, which follow the magic moves.
+pub const PEXT_TABLE_OFFSET: usize = magic::SLIDING_MOVE_TABLE_SIZE;
+
+#[inline(always)]
+pub fn get_rook_moves_index(square: Square, blockers: BitBoard) -> usize {
+    if pext_is_fast() {
+        // SAFETY: pext_is_fast only returns true if the CPU supports BMI2.
+        PEXT_TABLE_OFFSET + unsafe { pext::get_rook_moves_index_bmi2(square, blockers) }
+    } else {
+        magic::get_rook_moves_index(square, blockers)
+    }
+}
+
+#[inline(always)]
+pub fn get_bishop_moves_index(square: Square, blockers: BitBoard) -> usize {
+    if pext_is_fast() {
+        // SAFETY: pext_is_fast only returns true if the CPU supports BMI2.
+        PEXT_TABLE_OFFSET + unsafe { pext::get_bishop_moves_index_bmi2(square, blockers) }
+    } else {
+        magic::get_bishop_moves_index(square, blockers)
+    }
+}
+
+pub const SLIDING_MOVE_TABLE_SIZE: usize = PEXT_TABLE_OFFSET + pext::SLIDING_MOVE_TABLE_SIZE;
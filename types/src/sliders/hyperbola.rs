@@ -0,0 +1,106 @@
+use crate::*;
+
+use super::common::*;
+
+// Hyperbola quintessence computes slider moves along a single line with a subtraction
+// in each direction, using a byte swap for the reverse direction. This only works for
+// lines with at most one square per rank, so ranks use a small table of first rank moves
+// instead. Together these need a few kilobytes instead of a table of moves for every
+// blocker configuration.
+// https://www.chessprogramming.org/Hyperbola_Quintessence
+
+struct LineMasks {
+    file: BitBoard,
+    diagonal: BitBoard,
+    anti_diagonal: BitBoard
+}
+
+const EMPTY_MASKS: LineMasks = LineMasks {
+    file: BitBoard::EMPTY,
+    diagonal: BitBoard::EMPTY,
+    anti_diagonal: BitBoard::EMPTY
+};
+
+const LINE_MASKS: &[LineMasks; Square::NUM] = &{
+    let mut masks = [EMPTY_MASKS; Square::NUM];
+    let mut i = 0;
+    while i < Square::NUM {
+        let square = Square::index_const(i);
+        let file = square.file() as i8;
+        let rank = square.rank() as i8;
+        let mut j = 0;
+        while j < Square::NUM {
+            let target = Square::index_const(j);
+            let target_file = target.file() as i8;
+            let target_rank = target.rank() as i8;
+            let bit = target.bitboard().0;
+            if i != j {
+                if target_file == file {
+                    masks[i].file.0 |= bit;
+                }
+                if target_file - file == target_rank - rank {
+                    masks[i].diagonal.0 |= bit;
+                }
+                if target_file - file == rank - target_rank {
+                    masks[i].anti_diagonal.0 |= bit;
+                }
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    masks
+};
+
+// Moves for a slider on each file of the first rank, indexed by the six inner blockers.
+const FIRST_RANK_MOVES: &[[u8; 64]; File::NUM] = &{
+    let mut table = [[0; 64]; File::NUM];
+    let mut file = 0;
+    while file < File::NUM {
+        let mut inner = 0;
+        while inner < 64 {
+            let square = Square::index_const(file);
+            let blockers = BitBoard((inner as u64) << 1);
+            table[file][inner] = get_rook_moves_slow(square, blockers).0 as u8;
+            inner += 1;
+        }
+        file += 1;
+    }
+    table
+};
+
+/// Moves along a line with at most one square per rank.
+#[inline(always)]
+const fn get_line_moves(square: Square, blockers: BitBoard, mask: BitBoard) -> BitBoard {
+    let slider = square.bitboard().0;
+    let blockers = blockers.0 & mask.0;
+    let forward = blockers.wrapping_sub(slider);
+    let reverse = blockers.swap_bytes()
+        .wrapping_sub(slider.swap_bytes())
+        .swap_bytes();
+    BitBoard((forward ^ reverse) & mask.0)
+}
+
+#[inline(always)]
+const fn get_rank_moves(square: Square, blockers: BitBoard) -> BitBoard {
+    let shift = square.rank() as u32 * 8;
+    let inner = (blockers.0 >> (shift + 1)) & 0x3F;
+    let moves = FIRST_RANK_MOVES[square.file() as usize][inner as usize];
+    BitBoard((moves as u64) << shift)
+}
+
+pub const fn get_rook_moves_hyperbola(square: Square, blockers: BitBoard) -> BitBoard {
+    let masks = &LINE_MASKS[square as usize];
+    BitBoard(
+        get_line_moves(square, blockers, masks.file).0 |
+        get_rank_moves(square, blockers).0
+    )
+}
+
+pub const fn get_bishop_moves_hyperbola(square: Square, blockers: BitBoard) -> BitBoard {
+    let masks = &LINE_MASKS[square as usize];
+    BitBoard(
+        get_line_moves(square, blockers, masks.diagonal).0 |
+        get_line_moves(square, blockers, masks.anti_diagonal).0
+    )
+}
@@ -2,15 +2,35 @@
 #![allow(missing_docs)]
 
 mod common;
+mod hyperbola;
 
-#[cfg(not(feature = "pext"))]
-mod magic;
-#[cfg(feature = "pext")]
-mod pext;
+// Both indexing schemes are always available, for runtime dispatch and for building the table.
+pub mod magic;
+pub mod pext;
+#[cfg(all(not(feature = "pext"), target_arch = "x86_64"))]
+mod detect;
+#[cfg(all(not(feature = "pext"), target_arch = "x86_64"))]
+mod dispatch;
 
 pub use common::*;
+pub use hyperbola::*;
 
-#[cfg(not(feature = "pext"))]
-pub use magic::*;
 #[cfg(feature = "pext")]
 pub use pext::*;
+#[cfg(all(not(feature = "pext"), target_arch = "x86_64"))]
+pub use detect::*;
+#[cfg(all(not(feature = "pext"), target_arch = "x86_64"))]
+pub use dispatch::*;
+#[cfg(all(not(feature = "pext"), not(target_arch = "x86_64")))]
+pub use magic::*;
+
+/// Check if [`get_rook_moves_index`] and [`get_bishop_moves_index`] index PEXT moves.
+#[inline(always)]
+pub fn uses_pext_index() -> bool {
+    #[cfg(feature = "pext")]
+    return true;
+    #[cfg(all(not(feature = "pext"), target_arch = "x86_64"))]
+    return pext_is_fast();
+    #[cfg(all(not(feature = "pext"), not(target_arch = "x86_64")))]
+    return false;
+}
@@ -2,14 +2,33 @@ use crate::*;
 
 use super::common::*;
 
-#[cfg(not(all(target_arch = "x86_64", target_feature = "bmi2")))]
+// The hyperbola feature takes precedence over pext and never uses PEXT indices.
+#[cfg(all(feature = "pext", not(feature = "hyperbola"), not(all(target_arch = "x86_64", target_feature = "bmi2"))))]
 compile_error!("pext feature can only be enabled if target has BMI2.");
 
+#[cfg(all(feature = "pext", target_arch = "x86_64", target_feature = "bmi2"))]
+#[inline(always)]
 fn pext_u64(a: u64, mask: u64) -> u64 {
-    // SAFETY: A compile error is raised if PEXT is not available. PEXT is always safe if available.
+    // SAFETY: This is only compiled if the target has BMI2. PEXT is always safe if available.
     unsafe { core::arch::x86_64::_pext_u64(a, mask) }
 }
 
+// Portable PEXT, used to build the table for runtime dispatch on any host.
+#[cfg(not(all(feature = "pext", target_arch = "x86_64", target_feature = "bmi2")))]
+const fn pext_u64(a: u64, mut mask: u64) -> u64 {
+    let mut result = 0;
+    let mut bit = 1;
+    while mask != 0 {
+        let lowest = mask & mask.wrapping_neg();
+        if a & lowest != 0 {
+            result |= bit;
+        }
+        mask ^= lowest;
+        bit <<= 1;
+    }
+    result
+}
+
 struct PextEntry {
     offset: u32,
     mask: BitBoard
@@ -56,6 +75,7 @@ const INDEX_DATA: &PextIndexData = {
     }
 };
 
+#[inline(always)]
 fn get_pext_index(index_data: &[PextEntry; Square::NUM], square: Square, blockers: BitBoard) -> usize {
     let index_data = &index_data[square as usize];
     let index = pext_u64(blockers.0, index_data.mask.0);
@@ -71,3 +91,45 @@ pub fn get_bishop_moves_index(square: Square, blockers: BitBoard) -> usize {
 }
 
 pub const SLIDING_MOVE_TABLE_SIZE: usize = INDEX_DATA.table_size;
+
+/// PEXT through inline assembly, which unlike the intrinsic can be inlined into
+/// functions compiled without BMI2.
+/// # Safety
+/// The CPU must support BMI2.
+#[cfg(all(not(feature = "pext"), target_arch = "x86_64"))]
+#[inline(always)]
+unsafe fn pext_u64_asm(a: u64, mask: u64) -> u64 {
+    let result;
+    core::arch::asm!(
+        "pext {}, {}, {}",
+        lateout(reg) result,
+        in(reg) a,
+        in(reg) mask,
+        options(pure, nomem, nostack, preserves_flags)
+    );
+    result
+}
+
+#[cfg(all(not(feature = "pext"), target_arch = "x86_64"))]
+#[inline(always)]
+unsafe fn get_pext_index_bmi2(index_data: &[PextEntry; Square::NUM], square: Square, blockers: BitBoard) -> usize {
+    let index_data = &index_data[square as usize];
+    let index = pext_u64_asm(blockers.0, index_data.mask.0);
+    index_data.offset as usize + index as usize
+}
+
+/// # Safety
+/// The CPU must support BMI2. See [`super::pext_is_fast`].
+#[cfg(all(not(feature = "pext"), target_arch = "x86_64"))]
+#[inline(always)]
+pub unsafe fn get_rook_moves_index_bmi2(square: Square, blockers: BitBoard) -> usize {
+    get_pext_index_bmi2(&INDEX_DATA.rook_data, square, blockers)
+}
+
+/// # Safety
+/// The CPU must support BMI2. See [`super::pext_is_fast`].
+#[cfg(all(not(feature = "pext"), target_arch = "x86_64"))]
+#[inline(always)]
+pub unsafe fn get_bishop_moves_index_bmi2(square: Square, blockers: BitBoard) -> usize {
+    get_pext_index_bmi2(&INDEX_DATA.bishop_data, square, blockers)
+}